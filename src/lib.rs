@@ -2,19 +2,43 @@ use std::{
   borrow::Cow,
   cmp,
   cmp::Ordering,
+  collections::{HashMap, HashSet},
   env,
   fmt::Debug,
   fs,
-  fs::FileType,
-  io::Error,
+  fs::{FileType, Metadata},
+  io::{self, Error, Read},
   ops::Deref,
-  path::{Path, PathBuf},
+  path::{is_separator, Component, Path, PathBuf, MAIN_SEPARATOR},
+  thread::{self, JoinHandle},
+  time::{Duration, Instant, SystemTime},
 };
 
 use egui::{
-  Align2, Context, Id, Key, Layout, Pos2, RichText, ScrollArea, TextEdit, Ui, Vec2, Window,
+  text::{CCursor, CCursorRange, LayoutJob, TextWrapping},
+  text_edit::TextEditState,
+  Align2, Color32, Context, Event, FontId, Id, Key, Label, Layout, Modifiers, Pos2, Rect, Response,
+  RichText, ScrollArea, SelectableLabel, Sense, Shape, TextEdit, TextureHandle, Ui, Vec2, Window,
 };
 
+/// Time after which typed letters start a new jump search.
+const JUMP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Maximum number of folders to count the items of per frame.
+const MAX_FOLDER_COUNTS_PER_FRAME: usize = 16;
+
+/// Maximum number of thumbnails to load per frame.
+const MAX_THUMBNAILS_PER_FRAME: usize = 4;
+
+/// Seconds to wait after typing in the path field before completing it.
+const PATH_COMPLETION_DELAY: f64 = 0.3;
+
+/// Size of a cell in the grid view.
+const GRID_CELL_SIZE: Vec2 = Vec2::new(96.0, 96.0);
+
+/// Size of the thumbnail in a grid cell.
+const GRID_THUMBNAIL_SIZE: f32 = 64.0;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 /// Dialog state.
 pub enum State {
@@ -28,6 +52,19 @@ pub enum State {
   Selected,
 }
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// Why the dialog closed.
+pub enum CloseReason {
+  /// Escape was pressed.
+  Escape,
+  /// The Cancel button was clicked.
+  CancelButton,
+  /// The window's close button was clicked.
+  WindowClose,
+  /// A selection was confirmed.
+  Confirmed,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 /// Dialog type.
 pub enum DialogType {
@@ -36,11 +73,143 @@ pub enum DialogType {
   SaveFile,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+/// File list sort column.
+pub enum SortBy {
+  #[default]
+  Name,
+  Size,
+  Modified,
+  Type,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+/// File list sort direction.
+pub enum SortOrder {
+  #[default]
+  Ascending,
+  Descending,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+/// File list row spacing.
+pub enum Density {
+  /// Rows as tall as the text.
+  #[default]
+  Compact,
+  /// Taller rows that are easier to hit on touch screens.
+  Comfortable,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+/// File list layout.
+pub enum ViewMode {
+  /// One file per row.
+  #[default]
+  List,
+  /// Wrapping cells with a thumbnail over the name.
+  Grid,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+/// Where names too long for the file list are shortened.
+pub enum TruncateStyle {
+  /// Names aren't shortened.
+  #[default]
+  None,
+  /// The start is replaced with an ellipsis.
+  Start,
+  /// The middle is replaced with an ellipsis.
+  Middle,
+  /// The end is replaced with an ellipsis.
+  End,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// Kind of a listed entry, without following symbolic links.
+pub enum FileKind {
+  /// Regular file.
+  File,
+  /// Folder.
+  Dir,
+  /// Symbolic link, whether or not its target exists.
+  Symlink,
+  /// Unix domain socket.
+  Socket,
+  /// Named pipe.
+  Fifo,
+  /// Block device, such as a disk.
+  BlockDevice,
+  /// Character device, such as a terminal.
+  CharDevice,
+  /// The entry couldn't be read, or is of another kind.
+  #[default]
+  Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+/// Display settings that can be saved and restored as a unit.
+pub struct ViewConfig {
+  /// Show hidden files.
+  pub show_hidden: bool,
+  /// Show system files.
+  pub show_system_files: bool,
+  /// List files in the order they were read.
+  pub preserve_order: bool,
+  /// Sort column.
+  pub sort_by: SortBy,
+  /// Sort direction.
+  pub sort_order: SortOrder,
+  /// Sort digits in names by their numeric value.
+  pub natural_sort: bool,
+  /// Ignore case when sorting names.
+  pub case_insensitive_sort: bool,
+  /// Shade every other row.
+  pub striped: bool,
+  /// File list layout.
+  pub view_mode: ViewMode,
+  /// Show each file's size and modified date next to its name.
+  pub details: bool,
+  /// Window's size, of its content without the title bar and frame.
+  pub default_size: Vec2,
+  /// Window's position.
+  pub default_pos: Option<Pos2>,
+}
+
+impl Default for ViewConfig {
+  fn default() -> Self {
+    Self {
+      show_hidden: false,
+      show_system_files: false,
+      preserve_order: false,
+      sort_by: SortBy::Name,
+      sort_order: SortOrder::Ascending,
+      natural_sort: false,
+      case_insensitive_sort: false,
+      striped: false,
+      view_mode: ViewMode::List,
+      details: false,
+      default_size: egui::vec2(512.0, 512.0),
+      default_pos: None,
+    }
+  }
+}
+
 /// `egui` component that represents `OpenFileDialog` or `SaveFileDialog`.
 pub struct FileDialog {
   /// Current opened path.
   path: PathBuf,
 
+  /// Path the dialog was opened with.
+  initial_path: PathBuf,
+
   /// Editable field with path.
   path_edit: String,
 
@@ -68,51 +237,378 @@ pub struct FileDialog {
   /// New Folder name text
   new_folder_name_text: Cow<'static, str>,
 
+  /// New File button text
+  new_file_button_text: Cow<'static, str>,
+
+  /// New File name text
+  new_file_name_text: Cow<'static, str>,
+
   /// Rename button text
   rename_button_text: Cow<'static, str>,
 
+  /// Save button hover text when new files aren't allowed
+  existing_file_hover_text: Cow<'static, str>,
+
+  /// Hint shown when navigating with a typed filename
+  location_changed_text: Cow<'static, str>,
+
+  /// Overwrite prompt text
+  overwrite_text: Cow<'static, str>,
+
+  /// Yes button text
+  yes_button_text: Cow<'static, str>,
+
+  /// No button text
+  no_button_text: Cow<'static, str>,
+
+  /// Name column text
+  name_column_text: Cow<'static, str>,
+
+  /// Size column text
+  size_column_text: Cow<'static, str>,
+
+  /// Modified column text
+  modified_column_text: Cow<'static, str>,
+
+  /// Type column text
+  type_column_text: Cow<'static, str>,
+
+  /// Delete button text
+  delete_button_text: Cow<'static, str>,
+
+  /// Delete prompt text
+  delete_text: Cow<'static, str>,
+
+  /// Copy Path menu item text
+  copy_path_text: Cow<'static, str>,
+
+  /// Text shown when changes aren't allowed in the current folder
+  read_only_folder_text: Cow<'static, str>,
+
+  /// Text shown when the save target is read-only
+  read_only_text: Cow<'static, str>,
+
+  /// Text shown when navigating outside of the root folder
+  outside_root_text: Cow<'static, str>,
+
+  /// Text shown when the save name doesn't match the file type
+  expected_type_text: Cow<'static, str>,
+
+  /// Text shown when the save name is in a folder that doesn't exist
+  missing_folder_text: Cow<'static, str>,
+
+  /// Retry button text
+  retry_button_text: Cow<'static, str>,
+
+  /// Status bar item count text
+  items_text: Cow<'static, str>,
+
+  /// Status bar selection count text
+  selected_text: Cow<'static, str>,
+
+  /// Select All button text
+  select_all_button_text: Cow<'static, str>,
+
+  /// Invert Selection button text
+  invert_selection_button_text: Cow<'static, str>,
+
+  /// Copy button text
+  copy_button_text: Cow<'static, str>,
+
+  /// Paste button text
+  paste_button_text: Cow<'static, str>,
+
   /// Refresh button hover text
   refresh_button_hover_text: Cow<'static, str>,
 
   /// Parent Folder button hover text
   parent_folder_button_hover_text: Cow<'static, str>,
 
+  /// Home button hover text
+  home_button_hover_text: Cow<'static, str>,
+
+  /// Back button hover text
+  back_button_hover_text: Cow<'static, str>,
+
+  /// Forward button hover text
+  forward_button_hover_text: Cow<'static, str>,
+
+  /// Recent locations button hover text
+  recent_button_hover_text: Cow<'static, str>,
+
+  /// List view button hover text
+  list_view_hover_text: Cow<'static, str>,
+
+  /// Grid view button hover text
+  grid_view_hover_text: Cow<'static, str>,
+
   /// File label text
   file_label_text: Cow<'static, str>,
 
   /// Show Hidden checkbox text
   show_hidden_checkbox_text: Cow<'static, str>,
 
+  /// File type label text
+  file_type_label_text: Cow<'static, str>,
+
+  /// Empty folder text
+  empty_folder_text: Cow<'static, str>,
+
+  /// No matching files text
+  no_matches_text: Cow<'static, str>,
+
+  /// Filter bar hint text
+  filter_hint_text: Cow<'static, str>,
+
+  /// Filter bar text.
+  filter_text: String,
+
+  /// Search field hint text
+  search_hint_text: Cow<'static, str>,
+
+  /// Search field text, cleared when the folder is read.
+  search: String,
+
+  /// Lowercase filter bar and search field text, updated when they change.
+  name_filters: (String, String),
+
+  /// Custom content shown when there are no files to list.
+  empty_view: Option<ViewFn>,
+
+  /// Custom items added to the context menu of listed files.
+  context_menu: Option<ContextMenuFn>,
+
+  /// Focus the filename field on the next frame to rename the selected file.
+  focus_filename: bool,
+
+  /// Name typed for a folder to create, while New Folder is asking for one.
+  creating_folder: Option<String>,
+
+  /// Focus the new folder's name on the next frame.
+  focus_new_folder: bool,
+
+  /// Save target and whether it can be written, read again only when the target changes.
+  save_writable: Option<(PathBuf, bool)>,
+
+  /// Custom content shown between the path field and the file list.
+  header: Option<ViewFn>,
+
   /// Files in directory.
   files: Result<Vec<FileInfo>, Error>,
 
+  /// Folder being read in the background, see `background_loading`.
+  loading: Option<JoinHandle<Result<Vec<FileInfo>, Error>>>,
+
+  /// Folder being re-read in the background while the current entries stay listed.
+  reloading: Option<JoinHandle<Result<Vec<FileInfo>, Error>>>,
+
+  /// Unfiltered entries of the folder read last, reused until it's read again.
+  cached_entries: Option<(PathBuf, Vec<FileInfo>)>,
+
+  /// Whether the app's window had focus last frame, see `refresh_on_focus`.
+  window_focused: bool,
+
+  /// When the folder was last read, see `refresh_interval`.
+  refreshed_at: Instant,
+
+  /// Letters typed to jump to an entry, see `type_jump_row`.
+  jump_buffer: String,
+
+  /// When a letter was last typed into `jump_buffer`.
+  jump_at: Instant,
+
+  /// Time until which the location changed hint is shown.
+  location_changed_until: Option<f64>,
+
+  /// Error from the last failed navigation, shown briefly.
+  navigation_error: Option<String>,
+
+  /// Time until which the navigation error is shown.
+  navigation_error_until: Option<f64>,
+
+  /// Previously visited directories, most recent last.
+  back_stack: Vec<PathBuf>,
+
+  /// Directories left by going back, most recent last.
+  forward_stack: Vec<PathBuf>,
+
+  /// Recently visited directories, most recent first.
+  recent: Vec<PathBuf>,
+
+  /// Maximum number of recently visited directories to remember.
+  recent_capacity: usize,
+
+  /// Directory that failed to be listed.
+  error_path: Option<PathBuf>,
+
+  /// Message from a rejected rename.
+  rename_error: Option<String>,
+
+  /// Cached number of items in listed folders, `None` if the folder couldn't be read.
+  folder_counts: HashMap<PathBuf, Option<usize>>,
+
+  /// Files copied for pasting.
+  copied_files: Vec<PathBuf>,
+
   /// Current dialog state.
   state: State,
 
+  /// Action waiting for the user to confirm it.
+  prompt: Option<Prompt>,
+
+  /// File list entry that keyboard navigation moves from.
+  focused_index: Option<usize>,
+
+  /// File list scroll offset and height from the last frame.
+  list_viewport: (f32, f32),
+
+  /// File list scroll offsets of visited folders.
+  scroll_offsets: HashMap<PathBuf, f32>,
+
+  /// Scroll offset to apply to the file list on the next frame.
+  pending_scroll: Option<f32>,
+
+  /// Rows being selected by dragging.
+  drag: Option<DragSelect>,
+
+  /// Path and start of the previewed file, or why it couldn't be read.
+  preview_content: Option<(PathBuf, Result<Vec<u8>, String>)>,
+
+  /// A folder was just opened by a single click, see `open_on_single_click`.
+  single_click_opened: bool,
+
+  /// Scroll the focused file into view on the next frame.
+  scroll_to_focused: bool,
+
+  /// File to select once the folder has been read.
+  pending_reveal: Option<PathBuf>,
+
+  /// Files to select once the folder has been read, see `set_selection`.
+  pending_selection: Vec<PathBuf>,
+
+  /// Selection was confirmed during or before the last `show` while staying open.
+  just_selected: bool,
+
+  /// A `show` call has returned since `just_selected` was set, so the next one clears it.
+  just_selected_shown: bool,
+
+  /// Why the dialog closed.
+  close_reason: Option<CloseReason>,
+
   /// Dialog type.
   dialog_type: DialogType,
 
   id: Option<Id>,
+  window_rect: Option<Rect>,
+
+  /// Size of the window's content last frame, which `default_size` sets.
+  content_size: Option<Vec2>,
+  on_geometry_change: Option<GeometryFn>,
+  on_select: Option<SelectFn>,
+  preview: Option<PreviewFn>,
+  preview_max_bytes: u64,
+  icon: Option<IconFn>,
+  file_icons: bool,
+  hide_extensions: bool,
+  on_select_multiple: Option<MultiSelectFn>,
   current_pos: Option<Pos2>,
   default_pos: Option<Pos2>,
   default_size: Vec2,
   anchor: Option<(Align2, Vec2)>,
   show_files_filter: Filter<PathBuf>,
+  named_filters: Vec<(String, Filter<PathBuf>)>,
+  named_filter: usize,
   filename_filter: Filter<String>,
+  filename_filter_min_len: usize,
+  filename_validator: Option<ValidateFn>,
+  can_create_in: Option<Filter<PathBuf>>,
+  selectable_filter: Option<Filter<PathBuf>>,
+  rename_hook: Option<RenameFn>,
+  display_name: Option<DisplayFn>,
+  sort: Option<SortFn>,
   range_start: Option<usize>,
   resizable: bool,
   rename: bool,
   new_folder: bool,
+  new_file: bool,
+  copy_paste: bool,
+  delete: bool,
+  details: bool,
+  default_extension: Option<String>,
+  force_extension: bool,
+  enforce_filter_on_save: bool,
+  selected_path: bool,
+  allow_new_files: bool,
+  striped: bool,
+  canonical_comparison: bool,
+  warn_on_navigate: bool,
+  create_missing_dirs: bool,
+  breadcrumbs: bool,
+  editing_path: bool,
+  path_completions: Vec<String>,
+  path_edited_at: Option<f64>,
+  auto_select_single: bool,
+  max_depth: Option<usize>,
+  root: Option<PathBuf>,
+  relative_to: Option<PathBuf>,
+  filter_bar: bool,
+  folder_counts_enabled: bool,
+  search_field: bool,
+  view_selector: bool,
+  show_broken_symlinks: bool,
+  follow_symlinks: bool,
+  keep_open_after_select: bool,
+  confirm_overwrite: bool,
   multi_select_enabled: bool,
+  filter_selection: bool,
   keep_on_top: bool,
   show_system_files: bool,
+  preserve_order: bool,
+  sort_by: SortBy,
+  sort_order: SortOrder,
+  natural_sort: bool,
+  case_insensitive_sort: bool,
+  sort_header: bool,
+
+  /// Bookmarked folders listed in the sidebar.
+  bookmarks: Vec<(String, PathBuf)>,
+  sidebar: bool,
+  home_button: bool,
+  row_height: Option<f32>,
+  path_field_width: Option<f32>,
+  filename_field_width: Option<f32>,
+  density: Density,
+  view_mode: ViewMode,
+  truncate_names: TruncateStyle,
+  thumbnail: Option<ThumbnailFn>,
+  thumbnails: HashMap<PathBuf, Option<TextureHandle>>,
+  open_on_single_click: bool,
+  filename_field: bool,
+  home_directory: Option<PathBuf>,
+  close_on_escape: bool,
+  confirm_on_enter: bool,
+  status_bar: bool,
+  background_loading: bool,
+  refresh_on_focus: bool,
+  refresh_interval: Option<Duration>,
 
   /// Show drive letters on Windows.
   #[cfg(windows)]
   show_drives: bool,
 
-  /// Show hidden files on unix systems.
-  #[cfg(unix)]
+  /// Function that names drives on Windows.
+  #[cfg(windows)]
+  drive_display: Option<DisplayFn>,
+
+  /// Function that filters drives on Windows.
+  #[cfg(windows)]
+  drive_filter: Filter<PathBuf>,
+
+  /// Cached labels of listed drives on Windows, `None` if the drive couldn't be queried.
+  #[cfg(windows)]
+  drive_labels: HashMap<PathBuf, Option<String>>,
+
+  /// Show hidden files.
   show_hidden: bool,
 }
 
@@ -121,12 +617,39 @@ impl Debug for FileDialog {
     let mut dbg = f.debug_struct("FileDialog");
     let dbg = dbg
       .field("path", &self.path)
+      .field("initial_path", &self.initial_path)
+      .field("back_stack", &self.back_stack)
+      .field("forward_stack", &self.forward_stack)
+      .field("recent", &self.recent)
+      .field("recent_capacity", &self.recent_capacity)
       .field("path_edit", &self.path_edit)
       .field("selected_file", &self.selected_file)
       .field("filename_edit", &self.filename_edit)
+      .field("filter_text", &self.filter_text)
+      .field("search", &self.search)
+      .field("name_filters", &self.name_filters)
       .field("files", &self.files)
+      .field("loading", &self.loading)
+      .field("reloading", &self.reloading)
+      .field("cached_entries", &self.cached_entries)
+      .field("window_focused", &self.window_focused)
+      .field("refreshed_at", &self.refreshed_at)
+      .field("jump_buffer", &self.jump_buffer)
+      .field("jump_at", &self.jump_at)
+      .field("creating_folder", &self.creating_folder)
+      .field("focus_new_folder", &self.focus_new_folder)
+      .field("save_writable", &self.save_writable)
+      .field("error_path", &self.error_path)
+      .field("rename_error", &self.rename_error)
+      .field("navigation_error", &self.navigation_error)
       .field("state", &self.state)
+      .field("prompt", &self.prompt)
+      .field("just_selected", &self.just_selected)
+      .field("just_selected_shown", &self.just_selected_shown)
+      .field("close_reason", &self.close_reason)
       .field("dialog_type", &self.dialog_type)
+      .field("window_rect", &self.window_rect)
+      .field("content_size", &self.content_size)
       .field("current_pos", &self.current_pos)
       .field("default_pos", &self.default_pos)
       .field("default_size", &self.default_size)
@@ -134,21 +657,103 @@ impl Debug for FileDialog {
       .field("resizable", &self.resizable)
       .field("rename", &self.rename)
       .field("new_folder", &self.new_folder)
+      .field("new_file", &self.new_file)
+      .field("copy_paste", &self.copy_paste)
+      .field("delete", &self.delete)
+      .field("details", &self.details)
+      .field("default_extension", &self.default_extension)
+      .field("force_extension", &self.force_extension)
+      .field("enforce_filter_on_save", &self.enforce_filter_on_save)
+      .field("copied_files", &self.copied_files)
+      .field("selected_path", &self.selected_path)
+      .field("allow_new_files", &self.allow_new_files)
+      .field("striped", &self.striped)
+      .field("canonical_comparison", &self.canonical_comparison)
+      .field("warn_on_navigate", &self.warn_on_navigate)
+      .field("create_missing_dirs", &self.create_missing_dirs)
+      .field("breadcrumbs", &self.breadcrumbs)
+      .field("editing_path", &self.editing_path)
+      .field("path_completions", &self.path_completions)
+      .field("path_edited_at", &self.path_edited_at)
+      .field("auto_select_single", &self.auto_select_single)
+      .field("max_depth", &self.max_depth)
+      .field("root", &self.root)
+      .field("relative_to", &self.relative_to)
+      .field("filter_bar", &self.filter_bar)
+      .field("folder_counts", &self.folder_counts)
+      .field("folder_counts_enabled", &self.folder_counts_enabled)
+      .field("search_field", &self.search_field)
+      .field("view_selector", &self.view_selector)
+      .field("show_broken_symlinks", &self.show_broken_symlinks)
+      .field("follow_symlinks", &self.follow_symlinks)
+      .field("keep_open_after_select", &self.keep_open_after_select)
+      .field("confirm_overwrite", &self.confirm_overwrite)
       .field("multi_select", &self.multi_select_enabled)
+      .field("filter_selection", &self.filter_selection)
       .field("range_start", &self.range_start)
+      .field("focused_index", &self.focused_index)
+      .field("scroll_offsets", &self.scroll_offsets)
+      .field("pending_reveal", &self.pending_reveal)
+      .field("pending_selection", &self.pending_selection)
+      .field("named_filter", &self.named_filter)
+      .field("filename_filter_min_len", &self.filename_filter_min_len)
       .field("keep_on_top", &self.keep_on_top)
-      .field("show_system_files", &self.show_system_files);
+      .field("show_hidden", &self.show_hidden)
+      .field("show_system_files", &self.show_system_files)
+      .field("preserve_order", &self.preserve_order)
+      .field("sort_by", &self.sort_by)
+      .field("sort_order", &self.sort_order)
+      .field("natural_sort", &self.natural_sort)
+      .field("case_insensitive_sort", &self.case_insensitive_sort)
+      .field("sort_header", &self.sort_header)
+      .field("bookmarks", &self.bookmarks)
+      .field("sidebar", &self.sidebar)
+      .field("home_button", &self.home_button)
+      .field("row_height", &self.row_height)
+      .field("path_field_width", &self.path_field_width)
+      .field("filename_field_width", &self.filename_field_width)
+      .field("density", &self.density)
+      .field("view_mode", &self.view_mode)
+      .field("truncate_names", &self.truncate_names)
+      .field("thumbnails", &self.thumbnails.len())
+      .field("open_on_single_click", &self.open_on_single_click)
+      .field("filename_field", &self.filename_field)
+      .field("home_directory", &self.home_directory)
+      .field("close_on_escape", &self.close_on_escape)
+      .field("confirm_on_enter", &self.confirm_on_enter)
+      .field("status_bar", &self.status_bar)
+      .field("background_loading", &self.background_loading)
+      .field("refresh_on_focus", &self.refresh_on_focus)
+      .field("refresh_interval", &self.refresh_interval)
+      .field("preview_max_bytes", &self.preview_max_bytes)
+      .field("file_icons", &self.file_icons)
+      .field("hide_extensions", &self.hide_extensions);
 
     // Closures don't implement std::fmt::Debug.
     // let dbg = dbg
     //   .field("shown_files_filter", &self.shown_files_filter)
-    //   .field("filename_filter", &self.filename_filter);
-
-    #[cfg(unix)]
-    let dbg = dbg.field("show_hidden", &self.show_hidden);
+    //   .field("named_filters", &self.named_filters)
+    //   .field("filename_filter", &self.filename_filter)
+    //   .field("filename_validator", &self.filename_validator)
+    //   .field("can_create_in", &self.can_create_in)
+    //   .field("selectable_filter", &self.selectable_filter)
+    //   .field("rename_hook", &self.rename_hook)
+    //   .field("display_name", &self.display_name)
+    //   .field("sort", &self.sort)
+    //   .field("on_geometry_change", &self.on_geometry_change)
+    //   .field("on_select", &self.on_select)
+    //   .field("icon", &self.icon)
+    //   .field("preview", &self.preview)
+    //   .field("thumbnail", &self.thumbnail)
+    //   .field("on_select_multiple", &self.on_select_multiple)
+    //   .field("empty_view", &self.empty_view)
+    //   .field("context_menu", &self.context_menu)
+    //   .field("header", &self.header);
 
     #[cfg(windows)]
-    let dbg = dbg.field("show_drives", &self.show_drives);
+    let dbg = dbg
+      .field("show_drives", &self.show_drives)
+      .field("drive_labels", &self.drive_labels);
 
     dbg.finish()
   }
@@ -157,6 +762,44 @@ impl Debug for FileDialog {
 /// Function that returns `true` if the path is accepted.
 pub type Filter<T> = Box<dyn Fn(&<T as Deref>::Target) -> bool + Send + Sync + 'static>;
 
+/// Function that validates a filename. Returns a message explaining why the filename is invalid.
+pub type ValidateFn = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync + 'static>;
+
+/// Function that validates a rename from the first path to the second. Returns the path to rename
+/// to, or a message explaining why the rename was rejected.
+pub type RenameFn = Box<dyn Fn(&Path, &Path) -> Result<PathBuf, String> + Send + Sync + 'static>;
+
+/// Function that receives the window's new rectangle after it was moved or resized.
+pub type GeometryFn = Box<dyn FnMut(Rect) + Send + Sync + 'static>;
+
+/// Function that receives the confirmed path.
+pub type SelectFn = Box<dyn FnMut(&Path) + Send + Sync + 'static>;
+
+/// Function that receives the confirmed paths in multi-select mode.
+pub type MultiSelectFn = Box<dyn FnMut(&[&Path]) + Send + Sync + 'static>;
+
+/// Function that returns the name to display for a path, or `None` for the default name.
+pub type DisplayFn = Box<dyn Fn(&Path) -> Option<String> + Send + Sync + 'static>;
+
+/// Function that returns the icon to show for a path, or `None` for the default icon.
+pub type IconFn = Box<dyn Fn(&Path) -> Option<String> + Send + Sync + 'static>;
+
+/// Function that loads a thumbnail for a file in the grid view.
+pub type ThumbnailFn =
+  Box<dyn FnMut(&Path, &Context) -> Option<TextureHandle> + Send + Sync + 'static>;
+
+/// Function that renders a preview of the start of a file.
+pub type PreviewFn = Box<dyn FnMut(&[u8], &mut Ui) + Send + Sync + 'static>;
+
+/// Function that orders two listed paths.
+pub type SortFn = Box<dyn Fn(&Path, &Path) -> Ordering + Send + Sync + 'static>;
+
+/// Function that adds items to the context menu of a listed file.
+pub type ContextMenuFn = Box<dyn Fn(&Path, &mut Ui) + Send + Sync + 'static>;
+
+/// Function that renders custom content inside the dialog.
+pub type ViewFn = Box<dyn FnMut(&mut Ui) + Send + Sync + 'static>;
+
 impl FileDialog {
   /// Create dialog that prompts the user to select a folder.
   pub fn select_folder(initial_path: Option<PathBuf>) -> Self {
@@ -187,50 +830,208 @@ impl FileDialog {
 
     let path_edit = path.to_str().unwrap_or_default().to_string();
     Self {
+      initial_path: path.clone(),
       path,
       path_edit,
       selected_file: None,
       filename_edit,
-      title: match dialog_type {
-        DialogType::SelectFolder => "📁  Select Folder",
-        DialogType::OpenFile => "📂  Open File",
-        DialogType::SaveFile => "💾  Save File",
-      }
-      .into(),
+      title: format!("{}{}", title_icon(dialog_type), default_title(dialog_type)).into(),
       open_button_text: "Open".into(),
       save_button_text: "Save".into(),
       cancel_button_text: "Cancel".into(),
       new_folder_button_text: "New Folder".into(),
       new_folder_name_text: "New folder".into(),
+      new_file_button_text: "New File".into(),
+      new_file_name_text: "New file".into(),
       rename_button_text: "Rename".into(),
+      existing_file_hover_text: "Select an existing file".into(),
+      location_changed_text: "Save location changed:".into(),
+      overwrite_text: "File already exists. Overwrite?".into(),
+      yes_button_text: "Yes".into(),
+      no_button_text: "No".into(),
+      name_column_text: "Name".into(),
+      size_column_text: "Size".into(),
+      modified_column_text: "Modified".into(),
+      type_column_text: "Type".into(),
+      delete_button_text: "Delete".into(),
+      delete_text: "Delete permanently?".into(),
+      copy_path_text: "Copy Path".into(),
+      read_only_folder_text: "Changes aren't allowed in this folder".into(),
+      read_only_text: "Read-only".into(),
+      outside_root_text: "Outside of the allowed folder".into(),
+      expected_type_text: "Expected file type".into(),
+      missing_folder_text: "Folder doesn't exist".into(),
+      retry_button_text: "Retry".into(),
+      items_text: "items".into(),
+      selected_text: "selected".into(),
+      select_all_button_text: "Select All".into(),
+      invert_selection_button_text: "Invert Selection".into(),
+      copy_button_text: "Copy".into(),
+      paste_button_text: "Paste".into(),
       refresh_button_hover_text: "Refresh".into(),
       parent_folder_button_hover_text: "Parent Folder".into(),
+      home_button_hover_text: "Home Folder".into(),
+      recent_button_hover_text: "Recent Locations".into(),
+      list_view_hover_text: "List View".into(),
+      grid_view_hover_text: "Grid View".into(),
+      back_button_hover_text: "Back".into(),
+      forward_button_hover_text: "Forward".into(),
       file_label_text: "File:".into(),
       show_hidden_checkbox_text: "Show Hidden".into(),
+      file_type_label_text: "Type:".into(),
+      empty_folder_text: "Folder is empty".into(),
+      no_matches_text: "No matching files".into(),
+      filter_hint_text: "Filter".into(),
+      filter_text: String::new(),
+      search_hint_text: "Search".into(),
+      search: String::new(),
+      name_filters: (String::new(), String::new()),
+      empty_view: None,
+      context_menu: None,
+      focus_filename: false,
+      creating_folder: None,
+      focus_new_folder: false,
+      save_writable: None,
+      header: None,
       files: Ok(Vec::new()),
+      loading: None,
+      reloading: None,
+      cached_entries: None,
+      window_focused: true,
+      refreshed_at: Instant::now(),
+      jump_buffer: String::new(),
+      jump_at: Instant::now(),
+      location_changed_until: None,
+      navigation_error: None,
+      navigation_error_until: None,
+      back_stack: Vec::new(),
+      forward_stack: Vec::new(),
+      recent: Vec::new(),
+      recent_capacity: 10,
+      error_path: None,
+      rename_error: None,
+      folder_counts: HashMap::new(),
+      copied_files: Vec::new(),
       state: State::Closed,
+      close_reason: None,
+      prompt: None,
+      focused_index: None,
+      list_viewport: (0.0, 0.0),
+      scroll_offsets: HashMap::new(),
+      pending_scroll: None,
+      scroll_to_focused: false,
+      single_click_opened: false,
+      drag: None,
+      pending_reveal: None,
+      pending_selection: Vec::new(),
+      just_selected: false,
+      just_selected_shown: false,
       dialog_type,
 
       id: None,
+      window_rect: None,
+      content_size: None,
+      on_geometry_change: None,
+      on_select: None,
+      preview: None,
+      preview_max_bytes: 64 * 1024,
+      preview_content: None,
+      icon: None,
+      file_icons: false,
+      hide_extensions: false,
+      on_select_multiple: None,
       current_pos: None,
       default_pos: None,
       default_size: egui::vec2(512.0, 512.0),
       anchor: None,
       show_files_filter: Box::new(|_| true),
+      named_filters: Vec::new(),
+      named_filter: 0,
       filename_filter: Box::new(|_| true),
+      filename_filter_min_len: 0,
+      filename_validator: None,
+      can_create_in: None,
+      selectable_filter: None,
+      rename_hook: None,
+      display_name: None,
+      sort: None,
       resizable: true,
       rename: true,
       new_folder: true,
+      new_file: false,
+      copy_paste: false,
+      delete: false,
+      details: false,
+      default_extension: None,
+      force_extension: false,
+      enforce_filter_on_save: false,
+      selected_path: false,
+      allow_new_files: true,
+      striped: false,
+      canonical_comparison: false,
+      warn_on_navigate: false,
+      create_missing_dirs: false,
+      breadcrumbs: false,
+      editing_path: false,
+      path_completions: Vec::new(),
+      path_edited_at: None,
+      auto_select_single: false,
+      max_depth: None,
+      root: None,
+      relative_to: None,
+      filter_bar: false,
+      folder_counts_enabled: false,
+      search_field: false,
+      view_selector: false,
+      show_broken_symlinks: false,
+      follow_symlinks: false,
+      keep_open_after_select: false,
+      confirm_overwrite: false,
 
       #[cfg(windows)]
       show_drives: true,
 
-      #[cfg(unix)]
+      #[cfg(windows)]
+      drive_display: None,
+
+      #[cfg(windows)]
+      drive_filter: Box::new(|_| true),
+
+      #[cfg(windows)]
+      drive_labels: HashMap::new(),
+
       show_hidden: false,
       multi_select_enabled: false,
+      filter_selection: false,
       range_start: None,
       keep_on_top: false,
       show_system_files: false,
+      preserve_order: false,
+      sort_by: SortBy::Name,
+      sort_order: SortOrder::Ascending,
+      natural_sort: false,
+      case_insensitive_sort: false,
+      sort_header: false,
+      bookmarks: Vec::new(),
+      sidebar: false,
+      home_button: true,
+      row_height: None,
+      path_field_width: None,
+      filename_field_width: None,
+      density: Density::Compact,
+      view_mode: ViewMode::List,
+      truncate_names: TruncateStyle::None,
+      thumbnail: None,
+      thumbnails: HashMap::new(),
+      open_on_single_click: false,
+      filename_field: true,
+      home_directory: None,
+      close_on_escape: true,
+      confirm_on_enter: true,
+      status_bar: false,
+      background_loading: false,
+      refresh_on_focus: false,
+      refresh_interval: None,
     }
   }
 
@@ -240,16 +1041,36 @@ impl FileDialog {
     self
   }
 
+  /// Set the extension added to the file name on save when it has none, e.g. `"txt"`.
+  pub fn default_extension(mut self, extension: impl Into<String>) -> Self {
+    let extension = extension.into();
+    self.default_extension = Some(extension.trim_start_matches('.').to_string());
+    self
+  }
+
+  /// Replace a different extension with the default one on save. Default is `false`.
+  pub fn force_extension(mut self, force: bool) -> Self {
+    self.force_extension = force;
+    self
+  }
+
+  /// Only save file names that the files filter and the selected file type would list, after the
+  /// default extension is added. Save is disabled with a hint naming the expected type otherwise.
+  /// Default is `false`.
+  pub fn enforce_filter_on_save(mut self, enforce: bool) -> Self {
+    self.enforce_filter_on_save = enforce;
+    self
+  }
+
   /// Set the window title text.
   pub fn title(mut self, title: &str) -> Self {
-    self.title = (match self.dialog_type {
-      DialogType::SelectFolder => "📁  ",
-      DialogType::OpenFile => "📂  ",
-      DialogType::SaveFile => "💾  ",
-    }
-    .to_string()
-      + title)
-      .into();
+    self.title = format!("{}{title}", title_icon(self.dialog_type)).into();
+    self
+  }
+
+  /// Set the window title text without the dialog type icon.
+  pub fn title_no_icon(mut self, title: &str) -> Self {
+    self.title = title.to_string().into();
     self
   }
 
@@ -283,6 +1104,18 @@ impl FileDialog {
     self
   }
 
+  /// Set the new file button text.
+  pub fn new_file_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.new_file_button_text = text;
+    self
+  }
+
+  /// Set the new file name text.
+  pub fn new_file_name_text(mut self, text: Cow<'static, str>) -> Self {
+    self.new_file_name_text = text;
+    self
+  }
+
   /// Set the refresh button hover text.
   pub fn refresh_button_hover_text(mut self, text: Cow<'static, str>) -> Self {
     self.refresh_button_hover_text = text;
@@ -295,620 +1128,3889 @@ impl FileDialog {
     self
   }
 
-  /// Set the rename button text.
-  pub fn rename_button_text(mut self, text: Cow<'static, str>) -> Self {
-    self.rename_button_text = text;
+  /// Set the home button hover text.
+  pub fn home_button_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.home_button_hover_text = text;
     self
   }
 
-  /// Set the file label text.
-  pub fn file_label_text(mut self, text: Cow<'static, str>) -> Self {
-    self.file_label_text = text;
+  /// Set the back button hover text.
+  pub fn back_button_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.back_button_hover_text = text;
     self
   }
 
-  /// Set the show hidden checkbox text.
-  pub fn show_hidden_checkbox_text(mut self, text: Cow<'static, str>) -> Self {
-    self.show_hidden_checkbox_text = text;
+  /// Set the forward button hover text.
+  pub fn forward_button_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.forward_button_hover_text = text;
     self
   }
 
-  /// Set the window ID.
-  pub fn id(mut self, id: impl Into<Id>) -> Self {
-    self.id = Some(id.into());
+  /// Set the recent locations button hover text.
+  pub fn recent_button_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.recent_button_hover_text = text;
     self
   }
 
-  /// Set the window anchor.
-  pub fn anchor(mut self, align: Align2, offset: impl Into<Vec2>) -> Self {
-    self.anchor = Some((align, offset.into()));
+  /// Set the list view button hover text.
+  pub fn list_view_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.list_view_hover_text = text;
     self
   }
 
-  /// Set the window's current position.
-  pub fn current_pos(mut self, current_pos: impl Into<Pos2>) -> Self {
-    self.current_pos = Some(current_pos.into());
+  /// Set the grid view button hover text.
+  pub fn grid_view_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.grid_view_hover_text = text;
     self
   }
 
-  /// Set the window's default position.
-  pub fn default_pos(mut self, default_pos: impl Into<Pos2>) -> Self {
-    self.default_pos = Some(default_pos.into());
+  /// Set the rename button text.
+  pub fn rename_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.rename_button_text = text;
     self
   }
 
-  /// Set the window's default size.
-  pub fn default_size(mut self, default_size: impl Into<Vec2>) -> Self {
-    self.default_size = default_size.into();
+  /// Set the save button hover text shown when new files aren't allowed.
+  pub fn existing_file_hover_text(mut self, text: Cow<'static, str>) -> Self {
+    self.existing_file_hover_text = text;
     self
   }
 
-  /// Enable/disable resizing the window. Default is `true`.
-  pub fn resizable(mut self, resizable: bool) -> Self {
-    self.resizable = resizable;
+  /// Set the hint text shown when navigating with a typed filename.
+  pub fn location_changed_text(mut self, text: Cow<'static, str>) -> Self {
+    self.location_changed_text = text;
     self
   }
 
-  /// Show the Rename button. Default is `true`.
-  pub fn show_rename(mut self, rename: bool) -> Self {
-    self.rename = rename;
+  /// Set the text asking whether to overwrite an existing file.
+  pub fn overwrite_text(mut self, text: Cow<'static, str>) -> Self {
+    self.overwrite_text = text;
     self
   }
 
-  /// Show the New Folder button. Default is `true`.
-  pub fn show_new_folder(mut self, new_folder: bool) -> Self {
-    self.new_folder = new_folder;
+  /// Set the yes button text.
+  pub fn yes_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.yes_button_text = text;
     self
   }
 
-  pub fn multi_select(mut self, multi_select: bool) -> Self {
-    self.multi_select_enabled = multi_select;
+  /// Set the no button text.
+  pub fn no_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.no_button_text = text;
     self
   }
 
-  pub fn has_multi_select(&self) -> bool {
-    self.multi_select_enabled
+  /// Set the name column text.
+  pub fn name_column_text(mut self, text: Cow<'static, str>) -> Self {
+    self.name_column_text = text;
+    self
   }
 
-  /// Show the mapped drives on Windows. Default is `true`.
-  #[cfg(windows)]
-  pub fn show_drives(mut self, drives: bool) -> Self {
-    self.show_drives = drives;
+  /// Set the size column text.
+  pub fn size_column_text(mut self, text: Cow<'static, str>) -> Self {
+    self.size_column_text = text;
     self
   }
 
-  /// Set a function to filter listed files.
-  pub fn show_files_filter(mut self, filter: Filter<PathBuf>) -> Self {
-    self.show_files_filter = filter;
+  /// Set the modified column text.
+  pub fn modified_column_text(mut self, text: Cow<'static, str>) -> Self {
+    self.modified_column_text = text;
     self
   }
 
-  /// Set a function to filter the selected filename.
-  pub fn filename_filter(mut self, filter: Filter<String>) -> Self {
-    self.filename_filter = filter;
+  /// Set the type column text.
+  pub fn type_column_text(mut self, text: Cow<'static, str>) -> Self {
+    self.type_column_text = text;
     self
   }
 
-  /// Set to true in order to keep this window on top of other windows. Default is `false`.
-  pub fn keep_on_top(mut self, keep_on_top: bool) -> Self {
-    self.keep_on_top = keep_on_top;
+  /// Set the delete button text.
+  pub fn delete_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.delete_button_text = text;
     self
   }
 
-  /// Set to true in order to show system files. Default is `false`.
-  pub fn show_system_files(mut self, show_system_files: bool) -> Self {
-    self.show_system_files = show_system_files;
+  /// Set the Copy Path context menu item text.
+  pub fn copy_path_text(mut self, text: Cow<'static, str>) -> Self {
+    self.copy_path_text = text;
     self
   }
 
-  /// Get the dialog type.
-  pub fn dialog_type(&self) -> DialogType {
-    self.dialog_type
+  /// Set the text shown when hovering buttons disabled by `can_create_in`.
+  pub fn read_only_folder_text(mut self, text: Cow<'static, str>) -> Self {
+    self.read_only_folder_text = text;
+    self
   }
 
-  /// Get the window's visibility.
-  pub fn visible(&self) -> bool {
-    self.state == State::Open
+  /// Set the save button hover text shown when the file or folder to save to is read-only.
+  pub fn read_only_text(mut self, text: Cow<'static, str>) -> Self {
+    self.read_only_text = text;
+    self
   }
 
-  /// Opens the dialog.
-  pub fn open(&mut self) {
-    self.state = State::Open;
-    self.refresh();
+  /// Set the text asking whether to delete the selection.
+  pub fn delete_text(mut self, text: Cow<'static, str>) -> Self {
+    self.delete_text = text;
+    self
   }
 
-  /// Resulting file path.
-  pub fn path(&self) -> Option<&Path> {
-    self.selected_file.as_ref().map(|info| info.path.as_path())
+  /// Set the text shown when navigation outside of the root folder is rejected.
+  pub fn outside_root_text(mut self, text: Cow<'static, str>) -> Self {
+    self.outside_root_text = text;
+    self
   }
 
-  /// Retrieves multi selection as a vector.
-  pub fn selection(&self) -> Vec<&Path> {
-    match self.files {
-      Ok(ref files) => files
-        .iter()
-        .filter_map(|info| {
-          if info.selected {
-            Some(info.path.as_path())
-          } else {
-            None
-          }
-        })
-        .collect(),
-      Err(_) => Vec::new(),
-    }
+  /// Set the text shown when the save name doesn't match the file type.
+  pub fn expected_type_text(mut self, text: Cow<'static, str>) -> Self {
+    self.expected_type_text = text;
+    self
   }
 
-  /// Currently mounted directory that is being shown in the dialog box
-  pub fn directory(&self) -> &Path {
-    self.path.as_path()
+  /// Set the text shown when the save name is in a folder that doesn't exist.
+  pub fn missing_folder_text(mut self, text: Cow<'static, str>) -> Self {
+    self.missing_folder_text = text;
+    self
   }
 
-  /// Set the dialog's current opened path
-  pub fn set_path(&mut self, path: impl Into<PathBuf>) {
-    self.path = path.into();
-    self.refresh();
+  /// Set the retry button text.
+  pub fn retry_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.retry_button_text = text;
+    self
   }
 
-  /// Dialog state.
-  pub fn state(&self) -> State {
-    self.state
+  /// Set the text following the item count in the status bar.
+  pub fn items_text(mut self, text: Cow<'static, str>) -> Self {
+    self.items_text = text;
+    self
   }
 
-  /// Returns true, if the file selection was confirmed.
-  pub fn selected(&self) -> bool {
-    self.state == State::Selected
+  /// Set the text following the selection count in the status bar.
+  pub fn selected_text(mut self, text: Cow<'static, str>) -> Self {
+    self.selected_text = text;
+    self
   }
 
-  fn open_selected(&mut self) {
-    if let Some(info) = &self.selected_file {
-      if info.is_dir() {
-        self.set_path(info.path.clone());
-      } else if self.dialog_type == DialogType::OpenFile {
-        self.confirm();
-      }
-    } else if self.multi_select_enabled && self.dialog_type == DialogType::OpenFile {
-      self.confirm();
-    }
+  /// Set the select all button text.
+  pub fn select_all_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.select_all_button_text = text;
+    self
   }
 
-  fn confirm(&mut self) {
-    self.state = State::Selected;
+  /// Set the invert selection button text.
+  pub fn invert_selection_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.invert_selection_button_text = text;
+    self
   }
 
-  fn refresh(&mut self) {
-    self.files = self.read_folder();
-    self.path_edit = String::from(self.path.to_str().unwrap_or_default());
-    self.select(None);
-    self.selected_file = None;
+  /// Set the copy button text.
+  pub fn copy_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.copy_button_text = text;
+    self
   }
 
-  fn select(&mut self, file: Option<FileInfo>) {
-    if let Some(info) = &file {
-      if !info.is_dir() {
-        get_file_name(info).clone_into(&mut self.filename_edit);
-      }
-    }
-    self.selected_file = file;
+  /// Set the paste button text.
+  pub fn paste_button_text(mut self, text: Cow<'static, str>) -> Self {
+    self.paste_button_text = text;
+    self
   }
 
-  fn select_reset_multi(&mut self, idx: usize) {
-    if let Ok(files) = &mut self.files {
-      let selected_val = files[idx].selected;
-      for file in files.iter_mut() {
-        file.selected = false;
-      }
-      files[idx].selected = !selected_val;
-      self.range_start = Some(idx);
-    }
+  /// Set the file label text.
+  pub fn file_label_text(mut self, text: Cow<'static, str>) -> Self {
+    self.file_label_text = text;
+    self
   }
 
-  fn select_switch_multi(&mut self, idx: usize) {
-    if let Ok(files) = &mut self.files {
-      files[idx].selected = !files[idx].selected;
-      if files[idx].selected {
-        self.range_start = Some(idx);
-      } else {
-        self.range_start = None;
-      }
-    } else {
-      self.range_start = None;
-    }
+  /// Set the show hidden checkbox text.
+  pub fn show_hidden_checkbox_text(mut self, text: Cow<'static, str>) -> Self {
+    self.show_hidden_checkbox_text = text;
+    self
   }
 
-  fn select_range(&mut self, idx: usize) {
-    if let Ok(files) = &mut self.files {
-      if let Some(range_start) = self.range_start {
-        let range = cmp::min(idx, range_start)..=cmp::max(idx, range_start);
-        for i in range {
-          files[i].selected = true;
-        }
-      }
-    }
+  /// Set the empty folder text.
+  pub fn empty_folder_text(mut self, text: Cow<'static, str>) -> Self {
+    self.empty_folder_text = text;
+    self
   }
 
-  fn can_save(&self) -> bool {
-    !self.filename_edit.is_empty() && (self.filename_filter)(self.filename_edit.as_str())
+  /// Set the text shown when no files match the filter bar or search field.
+  pub fn no_matches_text(mut self, text: Cow<'static, str>) -> Self {
+    self.no_matches_text = text;
+    self
   }
 
-  fn can_open(&self) -> bool {
-    if self.multi_select_enabled {
-      if let Ok(files) = &self.files {
-        for file in files {
-          if file.selected && (self.filename_filter)(get_file_name(file)) {
-            return true;
-          }
-        }
-      }
-      false
-    } else {
-      !self.filename_edit.is_empty() && (self.filename_filter)(self.filename_edit.as_str())
-    }
+  /// Set the filter bar hint text.
+  pub fn filter_hint_text(mut self, text: Cow<'static, str>) -> Self {
+    self.filter_hint_text = text;
+    self
   }
 
-  fn can_rename(&self) -> bool {
-    if !self.filename_edit.is_empty() {
-      if let Some(file) = &self.selected_file {
-        return get_file_name(file) != self.filename_edit;
-      }
-    }
-    false
+  /// Set the search field hint text.
+  pub fn search_hint_text(mut self, text: Cow<'static, str>) -> Self {
+    self.search_hint_text = text;
+    self
   }
 
-  /// Shows the dialog if it is open. It is also responsible for state management.
-  /// Should be called every ui update.
-  pub fn show(&mut self, ctx: &Context) -> &Self {
-    self.state = match self.state {
-      State::Open => {
-        if ctx.input(|state| state.key_pressed(Key::Escape)) {
-          self.state = State::Cancelled;
-        }
+  /// Set a function to render the file list area when there are no files to show.
+  /// Listing errors are still shown as an error label.
+  pub fn empty_view(mut self, view: ViewFn) -> Self {
+    self.empty_view = Some(view);
+    self
+  }
 
-        let mut is_open = true;
-        self.ui(ctx, &mut is_open);
-        match is_open {
-          true => self.state,
-          false => State::Cancelled,
-        }
-      }
-      _ => State::Closed,
-    };
+  /// Set a function that adds items to the context menu of listed files, after the built-in Rename,
+  /// Delete and Copy Path items. It's called with the right-clicked file's path.
+  pub fn context_menu(mut self, context_menu: ContextMenuFn) -> Self {
+    self.context_menu = Some(context_menu);
+    self
+  }
 
+  /// Set a function to render app widgets between the path field and the file list. It's called
+  /// every frame while the dialog is shown and should only be used for the app's own controls.
+  pub fn header(mut self, header: ViewFn) -> Self {
+    self.header = Some(header);
     self
   }
 
-  fn ui(&mut self, ctx: &Context, is_open: &mut bool) {
-    let mut window = Window::new(RichText::new(self.title.as_ref()).strong())
-      .open(is_open)
-      .default_size(self.default_size)
-      .resizable(self.resizable)
-      .collapsible(false);
+  /// Set the file type label text.
+  pub fn file_type_label_text(mut self, text: Cow<'static, str>) -> Self {
+    self.file_type_label_text = text;
+    self
+  }
 
-    if let Some(id) = self.id {
-      window = window.id(id);
-    }
+  /// Set the window ID.
+  pub fn id(mut self, id: impl Into<Id>) -> Self {
+    self.id = Some(id.into());
+    self
+  }
 
-    if let Some((align, offset)) = self.anchor {
-      window = window.anchor(align, offset);
-    }
+  /// Set the window anchor.
+  pub fn anchor(mut self, align: Align2, offset: impl Into<Vec2>) -> Self {
+    self.anchor = Some((align, offset.into()));
+    self
+  }
 
-    if let Some(current_pos) = self.current_pos {
-      window = window.current_pos(current_pos);
-    }
+  /// Set the window's current position.
+  pub fn current_pos(mut self, current_pos: impl Into<Pos2>) -> Self {
+    self.current_pos = Some(current_pos.into());
+    self
+  }
 
-    if let Some(default_pos) = self.default_pos {
-      window = window.default_pos(default_pos);
-    }
+  /// Set the window's default position.
+  pub fn default_pos(mut self, default_pos: impl Into<Pos2>) -> Self {
+    self.default_pos = Some(default_pos.into());
+    self
+  }
 
-    window.show(ctx, |ui| {
-      if self.keep_on_top {
-        ui.ctx().move_to_top(ui.layer_id());
-      }
-      self.ui_in_window(ui)
-    });
+  /// Set a function that's called when the window is moved or resized.
+  pub fn on_geometry_change(mut self, on_geometry_change: GeometryFn) -> Self {
+    self.on_geometry_change = Some(on_geometry_change);
+    self
   }
 
-  fn ui_in_window(&mut self, ui: &mut Ui) {
-    enum Command {
-      Cancel,
-      CreateDirectory,
-      Folder,
-      Open(FileInfo),
-      OpenSelected,
-      BrowseDirectory(FileInfo),
-      Refresh,
-      Rename(PathBuf, PathBuf),
-      Save(FileInfo),
-      Select(FileInfo),
-      MultiSelectRange(usize),
-      MultiSelect(usize),
-      MultiSelectSwitch(usize),
-      UpDirectory,
-    }
-    let mut command: Option<Command> = None;
+  /// Set a function that's called once with the path when a selection is confirmed. It runs inside
+  /// the `show` call that confirms the selection, so it has already run when `selected()` first
+  /// returns `true`. Not called in multi-select mode, see `on_select_multiple`.
+  pub fn on_select(mut self, on_select: SelectFn) -> Self {
+    self.on_select = Some(on_select);
+    self
+  }
 
-    // Top directory field with buttons.
-    egui::TopBottomPanel::top("egui_file_top").show_inside(ui, |ui| {
-      ui.horizontal(|ui| {
-        ui.add_enabled_ui(self.path.parent().is_some(), |ui| {
-          let response = ui
-            .button("⬆")
-            .on_hover_text(self.parent_folder_button_hover_text.as_ref());
-          if response.clicked() {
-            command = Some(Command::UpDirectory);
-          }
-        });
-        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-          let response = ui
-            .button("⟲")
-            .on_hover_text(self.refresh_button_hover_text.as_ref());
-          if response.clicked() {
-            command = Some(Command::Refresh);
-          }
+  /// Set a function that's called once with the selected paths when a multi-select selection is
+  /// confirmed. Like `on_select`, it runs before `selected()` first returns `true`.
+  pub fn on_select_multiple(mut self, on_select_multiple: MultiSelectFn) -> Self {
+    self.on_select_multiple = Some(on_select_multiple);
+    self
+  }
 
-          let response = ui.add_sized(
-            ui.available_size(),
-            TextEdit::singleline(&mut self.path_edit),
-          );
+  /// Set a function that previews the selected file in a panel beside the list. It receives the
+  /// start of the file, which is only read when the selection changes.
+  pub fn preview(mut self, preview: PreviewFn) -> Self {
+    self.preview = Some(preview);
+    self
+  }
 
-          if response.lost_focus() {
-            let path = PathBuf::from(&self.path_edit);
-            command = Some(Command::Open(FileInfo::new(path)));
-          }
-        });
-      });
-      ui.add_space(ui.spacing().item_spacing.y);
-    });
+  /// Set the most bytes read from a file for the preview. Default is 64 KiB.
+  pub fn preview_max_bytes(mut self, max_bytes: u64) -> Self {
+    self.preview_max_bytes = max_bytes;
+    self
+  }
 
-    // Bottom file field.
-    egui::TopBottomPanel::bottom("egui_file_bottom").show_inside(ui, |ui| {
-      ui.add_space(ui.spacing().item_spacing.y * 2.0);
-      ui.horizontal(|ui| {
-        ui.label(self.file_label_text.as_ref());
-        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-          if self.new_folder && ui.button(self.new_folder_button_text.as_ref()).clicked() {
-            command = Some(Command::CreateDirectory);
-          }
+  /// Set a function that returns the icon for an entry. Returning `None` uses the default icon.
+  pub fn icon_fn(mut self, icon: IconFn) -> Self {
+    self.icon = Some(icon);
+    self
+  }
 
-          if self.rename {
-            ui.add_enabled_ui(self.can_rename(), |ui| {
-              if ui.button(self.rename_button_text.as_ref()).clicked() {
-                if let Some(from) = self.selected_file.clone() {
-                  let to = from.path.with_file_name(&self.filename_edit);
-                  command = Some(Command::Rename(from.path, to));
-                }
-              }
-            });
-          }
+  /// Show icons for common file types, such as images and archives, based on the extension.
+  /// Default is `false`.
+  pub fn show_file_icons(mut self, file_icons: bool) -> Self {
+    self.file_icons = file_icons;
+    self
+  }
 
-          let response = ui.add_sized(
-            ui.available_size(),
-            TextEdit::singleline(&mut self.filename_edit),
-          );
+  /// Leave the extensions off of file names in the list. Selected and returned paths keep them.
+  /// Default is `false`.
+  pub fn hide_extensions(mut self, hide_extensions: bool) -> Self {
+    self.hide_extensions = hide_extensions;
+    self
+  }
 
-          if response.lost_focus() {
-            let ctx = response.ctx;
-            let enter_pressed = ctx.input(|state| state.key_pressed(Key::Enter));
+  /// Set the window's default size.
+  pub fn default_size(mut self, default_size: impl Into<Vec2>) -> Self {
+    self.default_size = default_size.into();
+    self
+  }
 
-            if enter_pressed && (self.filename_filter)(self.filename_edit.as_str()) {
-              let path = self.path.join(&self.filename_edit);
-              match self.dialog_type {
-                DialogType::SelectFolder => command = Some(Command::Folder),
-                DialogType::OpenFile => {
-                  if path.exists() {
-                    command = Some(Command::Open(FileInfo::new(path)));
-                  }
-                }
-                DialogType::SaveFile => {
-                  let file_info = FileInfo::new(path);
-                  command = Some(match file_info.is_dir() {
-                    true => Command::Open(file_info),
-                    false => Command::Save(file_info),
-                  });
-                }
-              }
-            }
-          }
-        });
-      });
+  /// Enable/disable resizing the window. Default is `true`.
+  pub fn resizable(mut self, resizable: bool) -> Self {
+    self.resizable = resizable;
+    self
+  }
 
-      ui.add_space(ui.spacing().item_spacing.y);
+  /// Show the Rename button. Default is `true`.
+  pub fn show_rename(mut self, rename: bool) -> Self {
+    self.rename = rename;
+    self
+  }
 
-      // Confirm, Cancel buttons.
-      ui.horizontal(|ui| {
-        match self.dialog_type {
-          DialogType::SelectFolder => {
-            ui.horizontal(|ui| {
+  /// Show the New Folder button. Default is `true`.
+  pub fn show_new_folder(mut self, new_folder: bool) -> Self {
+    self.new_folder = new_folder;
+    self
+  }
+
+  /// Show the New File button, which creates an empty file. Default is `false`.
+  pub fn show_new_file(mut self, new_file: bool) -> Self {
+    self.new_file = new_file;
+    self
+  }
+
+  /// Show each file's size and modified date (UTC) next to its name. Names too long to fit beside
+  /// them are shortened, see `truncate_names`. Default is `false`.
+  pub fn show_details(mut self, details: bool) -> Self {
+    self.details = details;
+    self
+  }
+
+  /// Set a function that decides whether files can be created, renamed or deleted in the current
+  /// folder. Where it returns `false`, the New Folder, New File, Paste, Rename and Delete buttons
+  /// are disabled.
+  pub fn can_create_in(mut self, can_create_in: Filter<PathBuf>) -> Self {
+    self.can_create_in = Some(can_create_in);
+    self
+  }
+
+  /// Show the Delete button. Deleting asks for confirmation first. Default is `false`.
+  pub fn show_delete(mut self, delete: bool) -> Self {
+    self.delete = delete;
+    self
+  }
+
+  /// Show the Copy and Paste buttons. Default is `false`.
+  pub fn show_copy_paste(mut self, copy_paste: bool) -> Self {
+    self.copy_paste = copy_paste;
+    self
+  }
+
+  /// Show the full path of the selected file above the confirm buttons. Default is `false`.
+  pub fn show_selected_path(mut self, selected_path: bool) -> Self {
+    self.selected_path = selected_path;
+    self
+  }
+
+  /// Allow saving to a file that doesn't exist yet. Default is `true`.
+  pub fn allow_new_files(mut self, allow_new_files: bool) -> Self {
+    self.allow_new_files = allow_new_files;
+    self
+  }
+
+  /// Set the height of the file list rows, overriding the density. By default it's the height of
+  /// the text.
+  pub fn row_height(mut self, height: f32) -> Self {
+    self.row_height = Some(height);
+    self
+  }
+
+  /// Set the width of the path field and breadcrumbs. `None` fills the rest of the row. Default is
+  /// `None`.
+  pub fn path_field_width(mut self, width: Option<f32>) -> Self {
+    self.path_field_width = width;
+    self
+  }
+
+  /// Set the width of the filename field. `None` fills the rest of the row. Default is `None`.
+  pub fn filename_field_width(mut self, width: Option<f32>) -> Self {
+    self.filename_field_width = width;
+    self
+  }
+
+  /// Set the file list row spacing. Default is `Density::Compact`.
+  pub fn density(mut self, density: Density) -> Self {
+    self.density = density;
+    self
+  }
+
+  /// Set the file list layout. Default is `ViewMode::List`.
+  pub fn view_mode(mut self, view_mode: ViewMode) -> Self {
+    self.view_mode = view_mode;
+    self
+  }
+
+  /// Shorten names that don't fit in the file list with an ellipsis, showing the full name on
+  /// hover. Only the displayed text is shortened. With `show_details`, names are always kept clear
+  /// of the details, at the end if this is `TruncateStyle::None`. Default is `TruncateStyle::None`.
+  pub fn truncate_names(mut self, style: TruncateStyle) -> Self {
+    self.truncate_names = style;
+    self
+  }
+
+  /// Set a function to load the thumbnails shown in the grid view. Files without a thumbnail show
+  /// their icon instead.
+  pub fn thumbnail_fn(mut self, thumbnail: ThumbnailFn) -> Self {
+    self.thumbnail = Some(thumbnail);
+    self
+  }
+
+  /// Shade every other row of the file list. Default is `false`.
+  pub fn striped(mut self, striped: bool) -> Self {
+    self.striped = striped;
+    self
+  }
+
+  /// Compare paths by their canonical form, resolving symlinks and `..` segments. Default is
+  /// `false`.
+  pub fn canonical_comparison(mut self, canonical_comparison: bool) -> Self {
+    self.canonical_comparison = canonical_comparison;
+    self
+  }
+
+  /// Briefly show a hint when the user navigates to another folder while a filename is typed in
+  /// save mode. Navigation isn't blocked. Default is `false`.
+  pub fn warn_on_navigate_with_filename(mut self, warn: bool) -> Self {
+    self.warn_on_navigate = warn;
+    self
+  }
+
+  /// Create missing folders when a path that doesn't exist is entered in the path field, or when
+  /// saving to a filename in folders that don't exist, in save mode. A path entered with an
+  /// extension names the file to save, so only its folder is created. Without this option,
+  /// saving into a missing folder is disabled. Default is `false`.
+  pub fn create_missing_dirs(mut self, create_missing_dirs: bool) -> Self {
+    self.create_missing_dirs = create_missing_dirs;
+    self
+  }
+
+  /// Show the current path as clickable folder segments. Clicking beside the segments switches to
+  /// editing the path as text. On Windows the drive segment opens a menu of drives. Default is
+  /// `false`.
+  pub fn breadcrumbs(mut self, breadcrumbs: bool) -> Self {
+    self.breadcrumbs = breadcrumbs;
+    self
+  }
+
+  /// Select the file automatically when a folder lists exactly one file. Folders are never
+  /// selected automatically and nothing is confirmed. Default is `false`.
+  pub fn auto_select_single(mut self, auto_select_single: bool) -> Self {
+    self.auto_select_single = auto_select_single;
+    self
+  }
+
+  /// Limit how many levels below the initial path the user can browse. Deeper folders are shown
+  /// disabled, and folders outside of the initial path can't be opened.
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Keep navigation inside the folder. Paths outside of it are rejected with a brief message.
+  /// The dialog starts in the folder if the initial path is outside of it.
+  pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+    let root = root.into();
+    let root = fs::canonicalize(&root).unwrap_or(root);
+    self.root = Some(root);
+    if !self.within_root(&self.path) {
+      self.path = self.root.clone().unwrap_or_default();
+      self.path_edit = self.path.to_string_lossy().into();
+    }
+    self
+  }
+
+  /// Return paths relative to the folder from `path` and `selection`, when they're inside it.
+  /// Navigation and callbacks still use absolute paths, see `path_absolute`.
+  pub fn relative_to(mut self, base: impl Into<PathBuf>) -> Self {
+    self.relative_to = Some(base.into());
+    self
+  }
+
+  /// Show a filter bar that narrows the listed files by name as you type. Default is `false`.
+  pub fn show_filter_bar(mut self, filter_bar: bool) -> Self {
+    self.filter_bar = filter_bar;
+    self
+  }
+
+  /// Show the number of items next to each folder. Counts are read as folders scroll into view.
+  /// Default is `false`.
+  pub fn show_folder_counts(mut self, folder_counts: bool) -> Self {
+    self.folder_counts_enabled = folder_counts;
+    self
+  }
+
+  /// Show symbolic links whose target is missing, marked with a warning sign. They're also shown
+  /// with `show_system_files`. Default is `false`.
+  pub fn show_broken_symlinks(mut self, show_broken_symlinks: bool) -> Self {
+    self.show_broken_symlinks = show_broken_symlinks;
+    self
+  }
+
+  /// Enter the target of a symbolically linked folder instead of showing it under the link's path.
+  /// Default is `false`.
+  pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+    self.follow_symlinks = follow_symlinks;
+    self
+  }
+
+  /// Keep the dialog open when a selection is confirmed. Instead of the state changing to
+  /// `State::Selected`, `just_selected` returns `true` for one frame. Default is `false`.
+  pub fn keep_open_after_select(mut self, keep_open: bool) -> Self {
+    self.keep_open_after_select = keep_open;
+    self
+  }
+
+  /// Ask before saving or renaming over an existing file. Default is `false`.
+  pub fn confirm_overwrite(mut self, confirm_overwrite: bool) -> Self {
+    self.confirm_overwrite = confirm_overwrite;
+    self
+  }
+
+  /// Show a search field beside the path field that hides files not matching it. The search is
+  /// cleared whenever the folder is read. Default is `false`.
+  pub fn search_field(mut self, search_field: bool) -> Self {
+    self.search_field = search_field;
+    self
+  }
+
+  /// Show buttons beside the refresh button that switch between the list and grid views. The
+  /// folder isn't read again when switching. Default is `false`.
+  pub fn show_view_selector(mut self, view_selector: bool) -> Self {
+    self.view_selector = view_selector;
+    self
+  }
+
+  pub fn multi_select(mut self, multi_select: bool) -> Self {
+    self.multi_select_enabled = multi_select;
+    self
+  }
+
+  /// Skip files that the filename filter rejects when selecting a range, selecting all or
+  /// dragging, and leave them out of `selection`. Files that can't be selected are always
+  /// skipped. Default is `false`.
+  pub fn filter_selection(mut self, filter_selection: bool) -> Self {
+    self.filter_selection = filter_selection;
+    self
+  }
+
+  pub fn has_multi_select(&self) -> bool {
+    self.multi_select_enabled
+  }
+
+  /// Show the mapped drives on Windows. Default is `true`.
+  #[cfg(windows)]
+  pub fn show_drives(mut self, drives: bool) -> Self {
+    self.show_drives = drives;
+    self
+  }
+
+  /// Set a function to name the drives listed on Windows, returning `None` for the default name.
+  #[cfg(windows)]
+  pub fn drive_display_fn(mut self, display: DisplayFn) -> Self {
+    self.drive_display = Some(display);
+    self
+  }
+
+  /// Set a function to filter the drives listed on Windows.
+  #[cfg(windows)]
+  pub fn drive_filter(mut self, filter: Filter<PathBuf>) -> Self {
+    self.drive_filter = filter;
+    self
+  }
+
+  /// Set a function to filter listed files.
+  pub fn show_files_filter(mut self, filter: Filter<PathBuf>) -> Self {
+    self.show_files_filter = filter;
+    self
+  }
+
+  /// Set a function to decide which listed files can be selected. Unlike `show_files_filter`, the
+  /// files it rejects are still listed, but greyed out and can't be clicked or selected.
+  pub fn selectable_filter(mut self, filter: Filter<PathBuf>) -> Self {
+    self.selectable_filter = Some(filter);
+    self
+  }
+
+  /// List only files with one of the extensions, ignoring case. Extensions may be given with or
+  /// without the leading dot. Folders are always listed. This sets the files filter, so a later
+  /// `show_files_filter` replaces it.
+  pub fn extensions(self, extensions: &[&str]) -> Self {
+    let extensions: Vec<String> = extensions
+      .iter()
+      .map(|ext| ext.trim_start_matches('.').to_lowercase())
+      .collect();
+
+    self.show_files_filter(Box::new(move |path| {
+      let ext = path.extension().and_then(|ext| ext.to_str());
+      ext.is_some_and(|ext| {
+        extensions
+          .iter()
+          .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+      })
+    }))
+  }
+
+  /// Add a named filter that the user can pick from a file type list. The first one added is
+  /// selected initially. It applies in addition to `show_files_filter`.
+  pub fn add_filter(mut self, name: impl Into<String>, filter: Filter<PathBuf>) -> Self {
+    self.named_filters.push((name.into(), filter));
+    self
+  }
+
+  /// Add a folder to the sidebar's bookmarks.
+  pub fn add_bookmark(mut self, label: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+    self.bookmarks.push((label.into(), path.into()));
+    self
+  }
+
+  /// Set how many recently visited folders are listed by the recent locations button beside the
+  /// path field. Zero hides the button. Default is `10`.
+  pub fn recent_capacity(mut self, capacity: usize) -> Self {
+    self.recent_capacity = capacity;
+    self.recent.truncate(capacity);
+    self
+  }
+
+  /// Show a sidebar with the bookmarks, and the drives on Windows. Default is `false`.
+  pub fn show_sidebar(mut self, sidebar: bool) -> Self {
+    self.sidebar = sidebar;
+    self
+  }
+
+  /// Show a status line with the number of items and selected items above the buttons. A folder
+  /// that can't be read shows its error there instead of in the file list. Default is `false`.
+  pub fn status_bar(mut self, status_bar: bool) -> Self {
+    self.status_bar = status_bar;
+    self
+  }
+
+  /// Read folders on a background thread, showing a spinner with a Cancel button until the files
+  /// arrive. Useful for slow network drives. The dialog can't tell whether a folder is readable
+  /// before entering it, so `set_path` doesn't fall back to the previous folder. Default is `false`.
+  pub fn background_loading(mut self, background_loading: bool) -> Self {
+    self.background_loading = background_loading;
+    self
+  }
+
+  /// Re-read the folder when the app's window regains focus, to pick up files changed by other
+  /// programs. The selection is kept. Default is `false`.
+  pub fn refresh_on_focus(mut self, refresh_on_focus: bool) -> Self {
+    self.refresh_on_focus = refresh_on_focus;
+    self
+  }
+
+  /// Re-read the folder periodically while the dialog is open, keeping the selection. Default is
+  /// to only read it when it's entered or refreshed.
+  pub fn refresh_interval(mut self, interval: Duration) -> Self {
+    self.refresh_interval = Some(interval);
+    self
+  }
+
+  /// Cancel the dialog when Escape is pressed. Default is `true`.
+  pub fn close_on_escape(mut self, close_on_escape: bool) -> Self {
+    self.close_on_escape = close_on_escape;
+    self
+  }
+
+  /// Confirm the typed filename, or open the selection, when Enter is pressed. Default is `true`.
+  pub fn confirm_on_enter(mut self, confirm_on_enter: bool) -> Self {
+    self.confirm_on_enter = confirm_on_enter;
+    self
+  }
+
+  /// Open folders with a single click instead of a double click. Files still need to be
+  /// confirmed. In multi-select mode, Shift and Ctrl clicks still select folders. Default is
+  /// `false`.
+  pub fn open_on_single_click(mut self, open_on_single_click: bool) -> Self {
+    self.open_on_single_click = open_on_single_click;
+    self
+  }
+
+  /// Show the filename field when opening files, otherwise only the list selection is confirmed.
+  /// Renaming needs the field, so the Rename button is hidden with it. The field is always shown
+  /// when saving and never when selecting a folder. Default is `true`.
+  pub fn show_filename_field(mut self, filename_field: bool) -> Self {
+    self.filename_field = filename_field;
+    self
+  }
+
+  /// Show a button that goes to the home folder. It's disabled if the home folder is unknown.
+  /// Default is `true`.
+  pub fn show_home_button(mut self, home_button: bool) -> Self {
+    self.home_button = home_button;
+    self
+  }
+
+  /// Set the folder the home button goes to, instead of the user's home folder.
+  pub fn home_directory(mut self, path: impl Into<PathBuf>) -> Self {
+    self.home_directory = Some(path.into());
+    self
+  }
+
+  /// Set a function to filter the selected filename.
+  pub fn filename_filter(mut self, filter: Filter<String>) -> Self {
+    self.filename_filter = filter;
+    self
+  }
+
+  /// Set a function to validate the selected filename. A filename it rejects can't be confirmed,
+  /// and the returned message is shown below the filename field.
+  pub fn filename_validator(mut self, validator: ValidateFn) -> Self {
+    self.filename_validator = Some(validator);
+    self
+  }
+
+  /// Set the minimum filename length before the filename filter is applied. Shorter filenames
+  /// can't be confirmed. Default is `0`.
+  pub fn filename_filter_min_len(mut self, min_len: usize) -> Self {
+    self.filename_filter_min_len = min_len;
+    self
+  }
+
+  /// Set a function that returns the name shown for a path in the file list, breadcrumbs and
+  /// prompts, or `None` to show the file name. Selection, the filename field and returned paths
+  /// still use the real file name.
+  pub fn display_name_fn(mut self, display_name: DisplayFn) -> Self {
+    self.display_name = Some(display_name);
+    self
+  }
+
+  /// Set a function to validate or adjust the target of a rename.
+  pub fn on_rename(mut self, hook: RenameFn) -> Self {
+    self.rename_hook = Some(hook);
+    self
+  }
+
+  /// Set to true in order to keep this window on top of other windows. Default is `false`.
+  pub fn keep_on_top(mut self, keep_on_top: bool) -> Self {
+    self.keep_on_top = keep_on_top;
+    self
+  }
+
+  /// Set to true in order to show system files. Default is `false`.
+  pub fn show_system_files(mut self, show_system_files: bool) -> Self {
+    self.show_system_files = show_system_files;
+    self
+  }
+
+  /// Set to true in order to list files in the order they were read instead of sorting them.
+  /// Default is `false`.
+  pub fn preserve_order(mut self, preserve_order: bool) -> Self {
+    self.preserve_order = preserve_order;
+    self
+  }
+
+  /// Set a function to order the listed files, replacing the sort column and folders-first
+  /// ordering. `SortOrder::Descending` still reverses it.
+  pub fn sort_fn(mut self, sort: SortFn) -> Self {
+    self.sort = Some(sort);
+    self
+  }
+
+  /// Set the file list sort column and direction. Folders are always listed before files.
+  /// Default is `SortBy::Name` and `SortOrder::Ascending`.
+  pub fn sort_by(mut self, sort_by: SortBy, sort_order: SortOrder) -> Self {
+    self.sort_by = sort_by;
+    self.sort_order = sort_order;
+    self
+  }
+
+  /// Sort runs of digits in names by their numeric value, so "file2" comes before "file10".
+  /// Default is `false`.
+  pub fn natural_sort(mut self, natural_sort: bool) -> Self {
+    self.natural_sort = natural_sort;
+    self
+  }
+
+  /// Ignore case when sorting names, so "apple" comes before "Zebra". Default is `false`.
+  pub fn case_insensitive_sort(mut self, case_insensitive_sort: bool) -> Self {
+    self.case_insensitive_sort = case_insensitive_sort;
+    self
+  }
+
+  /// Show a row of clickable column headers above the file list for changing the sort.
+  /// Default is `false`.
+  pub fn show_sort_header(mut self, sort_header: bool) -> Self {
+    self.sort_header = sort_header;
+    self
+  }
+
+  /// Get the current display settings.
+  pub fn view_config(&self) -> ViewConfig {
+    // The window's current geometry once it's been shown, so that resizing and moving it are kept.
+    let (default_size, default_pos) = match (self.window_rect, self.content_size) {
+      (Some(rect), Some(size)) => (size, Some(rect.min)),
+      _ => (self.default_size, self.default_pos),
+    };
+
+    ViewConfig {
+      show_hidden: self.show_hidden,
+      show_system_files: self.show_system_files,
+      preserve_order: self.preserve_order,
+      sort_by: self.sort_by,
+      sort_order: self.sort_order,
+      natural_sort: self.natural_sort,
+      case_insensitive_sort: self.case_insensitive_sort,
+      striped: self.striped,
+      view_mode: self.view_mode,
+      details: self.details,
+      default_size,
+      default_pos,
+    }
+  }
+
+  /// Apply previously saved display settings.
+  pub fn apply_view_config(&mut self, config: &ViewConfig) {
+    self.show_hidden = config.show_hidden;
+    self.show_system_files = config.show_system_files;
+    self.preserve_order = config.preserve_order;
+    self.sort_by = config.sort_by;
+    self.sort_order = config.sort_order;
+    self.natural_sort = config.natural_sort;
+    self.case_insensitive_sort = config.case_insensitive_sort;
+    self.striped = config.striped;
+    self.view_mode = config.view_mode;
+    self.details = config.details;
+    self.default_size = config.default_size;
+    self.default_pos = config.default_pos;
+
+    if self.visible() {
+      self.refresh();
+    }
+  }
+
+  /// Get the dialog type.
+  pub fn dialog_type(&self) -> DialogType {
+    self.dialog_type
+  }
+
+  /// Change the dialog type, e.g. to switch between opening and saving in one dialog. The folder
+  /// and selection are kept. The title's icon follows the type, and a default title is replaced
+  /// with the new type's. Switching to selecting a folder clears the file selection. Set an `id` to
+  /// keep the window in place, since its default ID comes from the title.
+  pub fn set_dialog_type(&mut self, dialog_type: DialogType) {
+    if dialog_type == self.dialog_type {
+      return;
+    }
+
+    let icon = title_icon(self.dialog_type);
+    let new_icon = title_icon(dialog_type);
+    let title = match self.title.strip_prefix(icon) {
+      Some(title) if title == default_title(self.dialog_type) => {
+        Some(format!("{new_icon}{}", default_title(dialog_type)))
+      }
+      Some(title) => Some(format!("{new_icon}{title}")),
+      None => None,
+    };
+
+    if let Some(title) = title {
+      self.title = title.into();
+    }
+
+    self.dialog_type = dialog_type;
+    self.prompt = None;
+    if dialog_type == DialogType::SelectFolder {
+      if self
+        .selected_file
+        .as_ref()
+        .is_some_and(|info| !info.is_dir())
+      {
+        self.selected_file = None;
+      }
+
+      if let Ok(files) = &mut self.files {
+        for info in files.iter_mut().filter(|info| !info.is_dir()) {
+          info.selected = false;
+        }
+      }
+      self.filename_edit.clear();
+    }
+  }
+
+  /// Get the window's visibility.
+  pub fn visible(&self) -> bool {
+    self.state == State::Open
+  }
+
+  /// Get the window's rectangle from the last time it was shown.
+  pub fn window_rect(&self) -> Option<Rect> {
+    self.window_rect
+  }
+
+  /// Forget the listing of the current folder, so that it's read again the next time the list is
+  /// refreshed instead of reusing the cached entries. Opening the dialog and the refresh button
+  /// always read it again.
+  pub fn invalidate_cache(&mut self) {
+    self.cached_entries = None;
+  }
+
+  /// Opens the dialog.
+  pub fn open(&mut self) {
+    self.state = State::Open;
+    self.close_reason = None;
+    self.invalidate_cache();
+
+    #[cfg(windows)]
+    self.drive_labels.clear();
+
+    self.refresh();
+  }
+
+  /// Why the dialog was last closed, or `None` if it hasn't closed since it was opened.
+  pub fn close_reason(&self) -> Option<CloseReason> {
+    self.close_reason
+  }
+
+  /// Resulting file path, relative to the `relative_to` folder if it's inside it.
+  pub fn path(&self) -> Option<&Path> {
+    self.path_absolute().map(|path| self.relative(path))
+  }
+
+  /// Resulting file path, ignoring `relative_to`.
+  pub fn path_absolute(&self) -> Option<&Path> {
+    self.selected_file.as_ref().map(|info| info.path.as_path())
+  }
+
+  /// Retrieves multi selection as a vector, relative to the `relative_to` folder for paths inside
+  /// it.
+  pub fn selection(&self) -> Vec<&Path> {
+    self
+      .selection_absolute()
+      .into_iter()
+      .map(|path| self.relative(path))
+      .collect()
+  }
+
+  /// Retrieves multi selection as a vector, ignoring `relative_to`.
+  pub fn selection_absolute(&self) -> Vec<&Path> {
+    match self.files {
+      Ok(ref files) => files
+        .iter()
+        .filter_map(|info| {
+          if info.selected && self.passes_selection_filter(info) {
+            Some(info.path.as_path())
+          } else {
+            None
+          }
+        })
+        .collect(),
+      Err(_) => Vec::new(),
+    }
+  }
+
+  /// Selects the rows swept by dragging across the list in multi-select mode.
+  fn drag_select(&mut self, ui: &Ui, rows: &[usize], pointer_row: Option<usize>, list_rect: Rect) {
+    let (pressed, down, pos, ctrl) = ui.input(|state| {
+      let pointer = &state.pointer;
+      let pos = pointer.interact_pos();
+      (
+        pointer.primary_pressed(),
+        pointer.primary_down(),
+        pos,
+        state.modifiers.ctrl,
+      )
+    });
+
+    let allowed: Vec<bool> = match &self.files {
+      Ok(files) => files
+        .iter()
+        .map(|info| self.passes_selection_filter(info))
+        .collect(),
+      Err(_) => Vec::new(),
+    };
+
+    let Ok(files) = &mut self.files else {
+      self.drag = None;
+      return;
+    };
+
+    if pressed {
+      self.drag = pointer_row.map(|row| DragSelect {
+        start: row,
+        current: row,
+        snapshot: files.iter().map(|info| info.selected).collect(),
+        additive: ctrl,
+      });
+      return;
+    }
+
+    let Some(drag) = &mut self.drag else {
+      return;
+    };
+
+    if !down || drag.snapshot.len() != files.len() {
+      self.drag = None;
+      return;
+    }
+
+    // Leaving the list cancels the drag and restores the previous selection.
+    if !pos.is_some_and(|pos| list_rect.contains(pos)) {
+      for (info, &selected) in files.iter_mut().zip(&drag.snapshot) {
+        info.selected = selected;
+      }
+      self.drag = None;
+      return;
+    }
+
+    let Some(row) = pointer_row.filter(|&row| row != drag.current) else {
+      return;
+    };
+
+    drag.current = row;
+    for (info, &selected) in files.iter_mut().zip(&drag.snapshot) {
+      info.selected = drag.additive && selected;
+    }
+
+    let swept = cmp::min(drag.start, row)..=cmp::max(drag.start, row);
+    for &idx in &rows[swept] {
+      files[idx].selected |= allowed[idx];
+    }
+    self.range_start = Some(rows[drag.start]);
+    self.focused_index = Some(rows[row]);
+  }
+
+  /// Reads the start of the selected file for the preview if the selection changed.
+  fn update_preview(&mut self) {
+    let path = match (&self.files, self.multi_select_enabled) {
+      (Ok(files), true) => self.focused_index.and_then(|idx| files.get(idx)),
+      (_, false) => self.selected_file.as_ref(),
+      (Err(_), true) => None,
+    }
+    .filter(|info| info.is_file())
+    .map(|info| &info.path);
+
+    let cached = self.preview_content.as_ref().map(|(path, _)| path);
+    if path == cached {
+      return;
+    }
+
+    self.preview_content = path.map(|path| {
+      let mut bytes = Vec::new();
+      let result = fs::File::open(path)
+        .and_then(|file| file.take(self.preview_max_bytes).read_to_end(&mut bytes))
+        .map(|_| bytes)
+        .map_err(|err| err.to_string());
+      (path.clone(), result)
+    });
+  }
+
+  /// Select every file shown in multi-select mode. Files hidden by the filter bar or search field
+  /// are left as they are.
+  pub fn select_all(&mut self) {
+    self.set_shown_selected(|_| true);
+  }
+
+  /// Invert the selection of the files shown in multi-select mode. Files hidden by the filter bar
+  /// or search field are left as they are.
+  pub fn invert_selection(&mut self) {
+    self.set_shown_selected(|selected| !selected);
+  }
+
+  fn set_shown_selected(&mut self, selected: impl Fn(bool) -> bool) {
+    if !self.multi_select_enabled {
+      return;
+    }
+
+    if let Ok(files) = &self.files {
+      let shown: Vec<(usize, bool)> = (0..files.len())
+        .filter(|&idx| self.is_shown(get_file_name(&files[idx])))
+        .map(|idx| (idx, self.passes_selection_filter(&files[idx])))
+        .collect();
+
+      if let Ok(files) = &mut self.files {
+        for (idx, allowed) in shown {
+          files[idx].selected = allowed && selected(files[idx].selected);
+        }
+      }
+    }
+    self.range_start = None;
+  }
+
+  /// Clear the selection, including the multi-select flags, without changing the filename field.
+  pub fn clear_selection(&mut self) {
+    self.selected_file = None;
+    self.focused_index = None;
+    self.range_start = None;
+    if let Ok(files) = &mut self.files {
+      for file in files.iter_mut() {
+        file.selected = false;
+      }
+    }
+  }
+
+  /// Current contents of the filename field, as the user is typing it.
+  pub fn filename(&self) -> &str {
+    &self.filename_edit
+  }
+
+  /// Set the filename field, e.g. to suggest another name after a failed save.
+  pub fn set_filename(&mut self, name: impl Into<String>) {
+    self.filename_edit = name.into();
+  }
+
+  /// Name of the active filter added with `add_filter`, or `None` if there are none. Check it
+  /// along with `path()` to tell which format the user chose to save as.
+  pub fn selected_filter(&self) -> Option<&str> {
+    self
+      .named_filters
+      .get(self.named_filter)
+      .map(|(name, _)| name.as_str())
+  }
+
+  /// Retrieves multi selection as a vector of owned paths.
+  pub fn selection_owned(&self) -> Vec<PathBuf> {
+    self
+      .selection()
+      .into_iter()
+      .map(Path::to_path_buf)
+      .collect()
+  }
+
+  /// Retrieves multi selection as a vector of owned paths and clears the selection.
+  pub fn take_selection(&mut self) -> Vec<PathBuf> {
+    let selection = self.selection_owned();
+    if let Ok(files) = &mut self.files {
+      for info in files {
+        info.selected = false;
+      }
+    }
+    selection
+  }
+
+  /// Path relative to the `relative_to` folder, or as is if it's outside of it.
+  fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+    let Some(base) = &self.relative_to else {
+      return path;
+    };
+
+    match path.strip_prefix(base) {
+      Ok(relative) if relative.as_os_str().is_empty() => Path::new("."),
+      Ok(relative) => relative,
+      Err(_) => path,
+    }
+  }
+
+  /// Currently mounted directory that is being shown in the dialog box
+  pub fn directory(&self) -> &Path {
+    self.path.as_path()
+  }
+
+  /// Files listed in the current folder, leaving out those hidden by the filter bar or search
+  /// field. Empty if the folder couldn't be read.
+  pub fn visible_files(&self) -> Vec<&FileInfo> {
+    match &self.files {
+      Ok(files) => files
+        .iter()
+        .filter(|info| self.is_shown(get_file_name(info)))
+        .collect(),
+      Err(_) => Vec::new(),
+    }
+  }
+
+  /// Error from reading the current folder, if it failed.
+  pub fn listing_error(&self) -> Option<&Error> {
+    self.files.as_ref().err()
+  }
+
+  /// Directory that failed to be listed, if the last read failed.
+  pub fn last_error_path(&self) -> Option<&Path> {
+    self.error_path.as_deref()
+  }
+
+  /// Current filter bar text.
+  pub fn filter_text(&self) -> &str {
+    &self.filter_text
+  }
+
+  /// Set the filter bar text. An empty string shows all files.
+  pub fn set_filter_text(&mut self, text: impl Into<String>) {
+    self.filter_text = text.into();
+    self.update_name_filters();
+  }
+
+  /// Set the dialog's current opened path. If the folder can't be read but the current one can,
+  /// the dialog stays in the current folder and briefly shows the error.
+  pub fn set_path(&mut self, path: impl Into<PathBuf>) {
+    let path = path.into();
+    if path == self.path {
+      self.invalidate_cache();
+      self.refresh();
+      return;
+    }
+
+    if !self.can_enter(&path) {
+      self.navigation_error = Some(format!("{}: {}", path.display(), self.outside_root_text));
+      self.navigation_error_until = None;
+      self.path_edit = self.path.to_string_lossy().into();
+      return;
+    }
+
+    let previous = self.change_path(path);
+    let previous_ok = self.files.is_ok();
+    self.refresh();
+
+    if let (Err(err), true) = (&self.files, previous_ok) {
+      self.navigation_error = Some(format!("{}: {err}", self.path.display()));
+      self.navigation_error_until = None;
+      let failed = std::mem::replace(&mut self.path, previous);
+      self.pending_scroll = self.scroll_offsets.get(&self.path).copied();
+      self.refresh();
+
+      // Reading the previous folder again cleared the failed one.
+      self.error_path = Some(failed);
+      return;
+    }
+
+    self.back_stack.push(previous);
+    self.forward_stack.clear();
+    self.add_recent();
+  }
+
+  /// Recently visited folders, most recent first.
+  pub fn recent_locations(&self) -> &[PathBuf] {
+    &self.recent
+  }
+
+  /// Forget the recently visited folders.
+  pub fn clear_recent(&mut self) {
+    self.recent.clear();
+  }
+
+  /// Move the current folder to the front of the recent locations.
+  fn add_recent(&mut self) {
+    if self.recent_capacity == 0 {
+      return;
+    }
+
+    self.recent.retain(|path| *path != self.path);
+    self.recent.insert(0, self.path.clone());
+    self.recent.truncate(self.recent_capacity);
+  }
+
+  /// Select the files that are in the current folder, e.g. to restore a previous
+  /// `selection_owned`. Paths elsewhere, and files that can't be selected, are ignored. Before the
+  /// dialog is opened, or while the folder is being read, the files are selected once it has been
+  /// read. Without multi-select only the first one is selected.
+  pub fn set_selection(&mut self, paths: &[PathBuf]) {
+    self.pending_selection = paths.to_vec();
+    if self.state == State::Open {
+      self.select_pending();
+    }
+  }
+
+  /// Browse into the folder, or select the file if it's in the current folder, as if it was
+  /// clicked. Files in other folders are ignored, see `reveal`.
+  pub fn select_path(&mut self, path: &Path) {
+    if path.is_dir() {
+      if self.can_browse(path) {
+        self.set_path(path);
+      }
+    } else if path.parent() == Some(self.path.as_path()) {
+      self.pending_reveal = Some(path.to_path_buf());
+      self.reveal_pending();
+    }
+  }
+
+  /// Browse to the folder containing the path, then select it and scroll it into view.
+  pub fn reveal(&mut self, path: &Path) {
+    let Some(parent) = path.parent() else {
+      return;
+    };
+
+    self.set_path(parent);
+    if self.path == parent {
+      self.pending_reveal = Some(path.to_path_buf());
+      self.reveal_pending();
+    }
+  }
+
+  /// Selects the paths waiting for `set_selection`, once the folder has been read.
+  fn select_pending(&mut self) {
+    if self.loading.is_some() || self.pending_selection.is_empty() {
+      return;
+    }
+
+    let paths = std::mem::take(&mut self.pending_selection);
+    let Ok(files) = &self.files else {
+      return;
+    };
+
+    // Files that can't be selected by clicking are skipped too.
+    let indices: Vec<usize> = files
+      .iter()
+      .enumerate()
+      .filter(|(_, info)| paths.contains(&info.path) && self.passes_selection_filter(info))
+      .map(|(idx, _)| idx)
+      .collect();
+
+    let Some(&first) = indices.first() else {
+      return;
+    };
+
+    if !self.multi_select_enabled {
+      self.select_index(first);
+      return;
+    }
+
+    if let Ok(files) = &mut self.files {
+      for (idx, info) in files.iter_mut().enumerate() {
+        info.selected = indices.contains(&idx);
+      }
+    }
+    self.range_start = Some(first);
+    self.focused_index = Some(first);
+  }
+
+  /// Selects the path waiting to be revealed, once the folder has been read.
+  fn reveal_pending(&mut self) {
+    if self.loading.is_some() {
+      return;
+    }
+
+    let Some(path) = self.pending_reveal.take() else {
+      return;
+    };
+
+    let idx = match &self.files {
+      Ok(files) => files.iter().position(|info| info.path == path),
+      Err(_) => None,
+    };
+
+    match idx {
+      Some(idx) => {
+        self.select_index(idx);
+        self.scroll_to_focused = true;
+      }
+      None => self.select(Some(FileInfo::new(path))),
+    }
+  }
+
+  /// Go back to the previously visited directory. Returns `false` if there is none, or it's
+  /// outside of the root or the maximum depth.
+  pub fn history_back(&mut self) -> bool {
+    match self.back_stack.pop().filter(|path| self.can_enter(path)) {
+      Some(path) => {
+        let current = self.change_path(path);
+        self.forward_stack.push(current);
+        self.refresh();
+        if self.files.is_ok() {
+          self.add_recent();
+        }
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Go forward to the directory left by going back. Returns `false` if there is none, or it's
+  /// outside of the root or the maximum depth.
+  pub fn history_forward(&mut self) -> bool {
+    match self.forward_stack.pop().filter(|path| self.can_enter(path)) {
+      Some(path) => {
+        let current = self.change_path(path);
+        self.back_stack.push(current);
+        self.refresh();
+        if self.files.is_ok() {
+          self.add_recent();
+        }
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Sets the path without reading it, remembering the list's scroll position in the current one.
+  /// Returns the previous path.
+  fn change_path(&mut self, path: PathBuf) -> PathBuf {
+    self
+      .scroll_offsets
+      .insert(self.path.clone(), self.list_viewport.0);
+    self.pending_scroll = Some(self.scroll_offsets.get(&path).copied().unwrap_or(0.0));
+    std::mem::replace(&mut self.path, path)
+  }
+
+  /// Create a folder in the current directory, then select it and scroll it into view. Returns the
+  /// new folder's path.
+  pub fn create_folder(&mut self, name: &str) -> io::Result<PathBuf> {
+    let path = self.path.join(name);
+    fs::create_dir(&path)?;
+    self.invalidate_cache();
+    self.refresh();
+    self.pending_reveal = Some(path.clone());
+    self.reveal_pending();
+    Ok(path)
+  }
+
+  /// Create an empty file in the current directory and select it. Fails if the file already
+  /// exists. Returns the new file's path.
+  pub fn create_file(&mut self, name: &str) -> io::Result<PathBuf> {
+    let path = self.path.join(name);
+    fs::OpenOptions::new()
+      .write(true)
+      .create_new(true)
+      .open(&path)?;
+    self.invalidate_cache();
+    self.refresh();
+    self.pending_reveal = Some(path.clone());
+    self.reveal_pending();
+    Ok(path)
+  }
+
+  /// Confirm the current selection or typed filename as if the Open/Save button was clicked.
+  /// Returns `false`, without changing the state, if the dialog isn't open or there is nothing
+  /// valid to confirm.
+  pub fn confirm_selection(&mut self) -> bool {
+    if self.state != State::Open {
+      return false;
+    }
+
+    match self.dialog_type {
+      DialogType::SelectFolder => {
+        let path = self.get_folder().to_owned();
+        self.selected_file = Some(FileInfo::new(path));
+      }
+      DialogType::OpenFile => {
+        if let Some(info) = self.typed_file().filter(|_| self.filename_accepted()) {
+          self.select(Some(info));
+        } else if !self.can_open() {
+          return false;
+        } else if !self.multi_select_enabled {
+          let is_file = self
+            .selected_file
+            .as_ref()
+            .is_some_and(|info| info.is_file());
+          if !is_file {
+            return false;
+          }
+        }
+      }
+      DialogType::SaveFile => {
+        if !self.can_save() {
+          return false;
+        }
+
+        let path = self.path.join(&self.filename_edit);
+        let info = FileInfo::new(path);
+        if info.is_dir() {
+          return false;
+        }
+
+        // Leave it to the user to confirm overwriting.
+        return self.save(info);
+      }
+    }
+
+    self.confirm();
+    true
+  }
+
+  /// Dialog state.
+  pub fn state(&self) -> State {
+    self.state
+  }
+
+  /// Returns true, if the file selection was confirmed.
+  pub fn selected(&self) -> bool {
+    self.state == State::Selected
+  }
+
+  /// Returns true if a selection was confirmed while `keep_open_after_select` is enabled. It's
+  /// set by the `show` call that handled the confirmation, or by `confirm_selection` between
+  /// frames, and is cleared by the `show` call after the one that returned with it set. Check it
+  /// once per frame right after `show`.
+  pub fn just_selected(&self) -> bool {
+    self.just_selected
+  }
+
+  fn open_selected(&mut self) {
+    if let Some(info) = &self.selected_file {
+      if info.is_dir() {
+        if self.can_browse(&info.path) {
+          let path = match self.follow_symlinks && info.symlink {
+            true => fs::canonicalize(&info.path).unwrap_or_else(|_| info.path.clone()),
+            false => info.path.clone(),
+          };
+          self.set_path(path);
+        }
+      } else if self.dialog_type == DialogType::OpenFile {
+        self.confirm();
+      }
+    } else if self.multi_select_enabled && self.dialog_type == DialogType::OpenFile {
+      self.confirm();
+    }
+  }
+
+  /// Returns `false` if the path is outside of the root folder.
+  fn within_root(&self, path: &Path) -> bool {
+    let Some(root) = &self.root else {
+      return true;
+    };
+
+    match fs::canonicalize(path) {
+      Ok(path) => path.starts_with(root),
+      Err(_) => {
+        let parent_dir = path.components().any(|c| c == Component::ParentDir);
+        !parent_dir && path.starts_with(root)
+      }
+    }
+  }
+
+  /// Returns `false` if the file is kept out of bulk selections: files that can't be selected,
+  /// and with `filter_selection`, files that the filename filter rejects.
+  fn passes_selection_filter(&self, info: &FileInfo) -> bool {
+    if !self.can_select(info) {
+      return false;
+    }
+
+    !self.filter_selection || info.is_dir() || (self.filename_filter)(get_file_name(info))
+  }
+
+  /// Returns `false` if the file is shown greyed out in the list: files when selecting a folder,
+  /// folders below the maximum depth and files rejected by `selectable_filter`.
+  fn can_select(&self, info: &FileInfo) -> bool {
+    self.passes_selectable_filter(info)
+      && match info.is_dir() {
+        true => self.can_browse(&info.path),
+        false => self.dialog_type != DialogType::SelectFolder,
+      }
+  }
+
+  /// Returns `false` if `selectable_filter` rejects the file.
+  fn passes_selectable_filter(&self, info: &FileInfo) -> bool {
+    match &self.selectable_filter {
+      Some(filter) => filter(&info.path),
+      None => true,
+    }
+  }
+
+  /// Returns `false` if the folder is deeper below the initial path than the maximum depth, or
+  /// isn't below it at all.
+  fn can_browse(&self, path: &Path) -> bool {
+    let Some(max_depth) = self.max_depth else {
+      return true;
+    };
+
+    match path.strip_prefix(&self.initial_path) {
+      Ok(rel) => {
+        let parent_dir = rel.components().any(|c| c == Component::ParentDir);
+        !parent_dir && rel.components().count() <= max_depth
+      }
+      Err(_) => false,
+    }
+  }
+
+  /// Returns `false` if the folder is outside of the root or out of the maximum depth.
+  fn can_enter(&self, path: &Path) -> bool {
+    self.within_root(path) && self.can_browse(path)
+  }
+
+  fn rename(&mut self, from: &Path, to: PathBuf) {
+    match fs::rename(from, &to) {
+      Ok(_) => {
+        self.invalidate_cache();
+        self.refresh();
+        self.select(Some(FileInfo::new(to)));
+      }
+      Err(err) => println!("Error while renaming: {err}"),
+    }
+  }
+
+  /// Confirm saving to the file, asking first if it would be overwritten. Returns `false` if
+  /// neither happened, such as when its folder couldn't be created.
+  fn save(&mut self, mut file: FileInfo) -> bool {
+    let path = self.with_default_extension(&file.path);
+    if path != file.path {
+      if let Some(name) = path.file_name() {
+        self.filename_edit = name.to_string_lossy().into();
+      }
+      file = FileInfo::new(path);
+    }
+
+    // Create the folders leading to the file, but never outside of the root.
+    if let Some(parent) = file.path.parent() {
+      if !self.within_root(parent) {
+        return false;
+      }
+
+      if !parent.is_dir() {
+        if !self.create_missing_dirs {
+          return false;
+        }
+
+        if let Err(err) = fs::create_dir_all(parent) {
+          println!("Error while creating directory: {err}");
+          return false;
+        }
+        self.invalidate_cache();
+      }
+    }
+
+    if self.confirm_overwrite && file.is_file() {
+      self.prompt = Some(Prompt::Overwrite(file));
+    } else {
+      self.selected_file = Some(file);
+      self.confirm();
+    }
+    true
+  }
+
+  fn confirm(&mut self) {
+    if self.keep_open_after_select {
+      self.just_selected = true;
+      self.just_selected_shown = false;
+    } else {
+      self.state = State::Selected;
+      self.close_reason = Some(CloseReason::Confirmed);
+    }
+
+    if self.multi_select_enabled {
+      // Taken for the call since the paths borrow the dialog.
+      if let Some(mut on_select_multiple) = self.on_select_multiple.take() {
+        on_select_multiple(&self.selection_absolute());
+        self.on_select_multiple = Some(on_select_multiple);
+      }
+    } else if let (Some(on_select), Some(info)) = (&mut self.on_select, &self.selected_file) {
+      on_select(&info.path);
+    }
+  }
+
+  fn refresh(&mut self) {
+    self.refreshed_at = Instant::now();
+    self.save_writable = None;
+    self.reloading = None;
+    if let Some(entries) = self.cached_entries() {
+      self.loading = None;
+      self.files = Ok(self.filter_entries(entries));
+    } else if self.background_loading {
+      let path = self.path.clone();
+      self.loading = Some(thread::spawn(move || read_entries(&path)));
+      self.files = Ok(Vec::new());
+    } else {
+      self.loading = None;
+      self.files = self.read_folder();
+    }
+
+    self.folder_counts.clear();
+    self.thumbnails.clear();
+    self.search.clear();
+    self.update_name_filters();
+    self.error_path = self.files.is_err().then(|| self.path.clone());
+    self.rename_error = None;
+    self.prompt = None;
+    self.creating_folder = None;
+    self.path_edit = String::from(self.path.to_str().unwrap_or_default());
+    self.select(None);
+    self.selected_file = None;
+    self.focused_index = None;
+
+    if self.auto_select_single {
+      self.select_single_file();
+    }
+    self.select_pending();
+  }
+
+  /// Select the only file in the list, if there is exactly one.
+  fn select_single_file(&mut self) {
+    let Ok(files) = &self.files else {
+      return;
+    };
+
+    let mut iter = files.iter().enumerate().filter(|(_, info)| !info.is_dir());
+    if let (Some((idx, _)), None) = (iter.next(), iter.next()) {
+      self.select_index(idx);
+    }
+  }
+
+  fn select(&mut self, file: Option<FileInfo>) {
+    self.rename_error = None;
+    match &file {
+      Some(info) if !info.is_dir() => get_file_name(info).clone_into(&mut self.filename_edit),
+      // Without the field the name always follows the selection.
+      _ if !self.filename_field_shown() => self.filename_edit.clear(),
+      _ => {}
+    }
+    self.selected_file = file;
+  }
+
+  /// Returns `false` if the filename field is hidden. It's always shown when saving and never
+  /// when selecting a folder.
+  fn filename_field_shown(&self) -> bool {
+    match self.dialog_type {
+      DialogType::SelectFolder => false,
+      DialogType::OpenFile => self.filename_field,
+      DialogType::SaveFile => true,
+    }
+  }
+
+  /// Select only the file at the index and move the keyboard focus to it.
+  fn select_index(&mut self, idx: usize) {
+    // Keyboard navigation can move onto a file that can't be selected.
+    let selectable = match &self.files {
+      Ok(files) => self.can_select(&files[idx]),
+      Err(_) => return,
+    };
+
+    self.focused_index = Some(idx);
+    if !selectable {
+      return;
+    }
+
+    let Ok(files) = &mut self.files else {
+      return;
+    };
+
+    if self.multi_select_enabled {
+      for (n, file) in files.iter_mut().enumerate() {
+        file.selected = n == idx;
+      }
+      self.range_start = Some(idx);
+    } else {
+      let info = files[idx].clone();
+      self.select(Some(info));
+    }
+  }
+
+  /// Get the row to move to for navigation keys pressed this frame.
+  fn key_nav_row(&self, ui: &Ui, rows: &[usize], columns: usize, page: usize) -> Option<usize> {
+    let current = self
+      .focused_index
+      .and_then(|idx| rows.iter().position(|&row| row == idx));
+    let last = rows.len().checked_sub(1)?;
+
+    ui.input(|state| {
+      if state.key_pressed(Key::ArrowDown) {
+        Some(current.map_or(0, |row| (row + columns).min(last)))
+      } else if state.key_pressed(Key::ArrowUp) {
+        Some(current.map_or(last, |row| row.saturating_sub(columns)))
+      } else if columns > 1 && state.key_pressed(Key::ArrowRight) {
+        Some(current.map_or(0, |row| (row + 1).min(last)))
+      } else if columns > 1 && state.key_pressed(Key::ArrowLeft) {
+        Some(current.map_or(last, |row| row.saturating_sub(1)))
+      } else if state.key_pressed(Key::PageDown) {
+        Some(current.map_or(0, |row| (row + page).min(last)))
+      } else if state.key_pressed(Key::PageUp) {
+        Some(current.map_or(0, |row| row.saturating_sub(page)))
+      } else if state.key_pressed(Key::Home) {
+        Some(0)
+      } else if state.key_pressed(Key::End) {
+        Some(last)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Get the row of the first entry starting with the letters typed so far.
+  fn type_jump_row(&mut self, ui: &Ui, rows: &[usize]) -> Option<usize> {
+    let typed: String = ui.input(|state| {
+      state
+        .events
+        .iter()
+        .filter_map(|event| match event {
+          Event::Text(text) => Some(text.as_str()),
+          _ => None,
+        })
+        .collect()
+    });
+    if typed.is_empty() {
+      return None;
+    }
+
+    if self.jump_at.elapsed() > JUMP_TIMEOUT {
+      self.jump_buffer.clear();
+    }
+    self.jump_at = Instant::now();
+    self.jump_buffer.push_str(&typed.to_lowercase());
+
+    let files = self.files.as_ref().ok()?;
+    rows.iter().position(|&idx| {
+      get_file_name(&files[idx])
+        .to_lowercase()
+        .starts_with(&self.jump_buffer)
+    })
+  }
+
+  fn select_reset_multi(&mut self, idx: usize) {
+    self.focused_index = Some(idx);
+    if let Ok(files) = &mut self.files {
+      let selected_val = files[idx].selected;
+      for file in files.iter_mut() {
+        file.selected = false;
+      }
+      files[idx].selected = !selected_val;
+      self.range_start = Some(idx);
+    }
+  }
+
+  fn select_switch_multi(&mut self, idx: usize) {
+    self.focused_index = Some(idx);
+    if let Ok(files) = &mut self.files {
+      files[idx].selected = !files[idx].selected;
+      if files[idx].selected {
+        self.range_start = Some(idx);
+      } else {
+        self.range_start = None;
+      }
+    } else {
+      self.range_start = None;
+    }
+  }
+
+  fn select_range(&mut self, idx: usize) {
+    if let (Ok(files), Some(range_start)) = (&self.files, self.range_start) {
+      // Skip files hidden by the filter bar or search field.
+      let range = cmp::min(idx, range_start)..=cmp::max(idx, range_start);
+      let shown: Vec<usize> = range
+        .filter(|&idx| self.is_shown(get_file_name(&files[idx])))
+        .filter(|&idx| self.passes_selection_filter(&files[idx]))
+        .collect();
+
+      if let Ok(files) = &mut self.files {
+        for idx in shown {
+          files[idx].selected = true;
+        }
+      }
+    }
+  }
+
+  /// Shows a folder read error with a Retry button. Returns `true` if Retry was clicked.
+  fn error_ui(&self, ui: &mut Ui, err: &Error) -> bool {
+    ui.horizontal(|ui| {
+      let retry = ui.button(self.retry_button_text.as_ref()).clicked();
+      let color = ui.visuals().error_fg_color;
+      ui.add(Label::new(RichText::new(format!("⚠ {err}")).color(color)).truncate());
+      retry
+    })
+    .inner
+  }
+
+  /// Returns `true` if the name matches the search field, ignoring case.
+  fn matches_search(&self, name: &str) -> bool {
+    matches_filter(&self.name_filters.1, name)
+  }
+
+  /// Returns `true` if the name passes both the filter bar and the search field.
+  fn is_shown(&self, name: &str) -> bool {
+    matches_filter(&self.name_filters.0, name) && self.matches_search(name)
+  }
+
+  /// Lowercase the filter bar and search field text once, rather than for every file.
+  fn update_name_filters(&mut self) {
+    self.name_filters = (self.filter_text.to_lowercase(), self.search.to_lowercase());
+  }
+
+  fn same_path(&self, a: &Path, b: &Path) -> bool {
+    if self.canonical_comparison {
+      if let (Ok(a), Ok(b)) = (fs::canonicalize(a), fs::canonicalize(b)) {
+        return a == b;
+      }
+    }
+    a == b
+  }
+
+  /// Returns `true` if the typed filename is long enough and passes the filename filter and
+  /// validator.
+  fn filename_accepted(&self) -> bool {
+    self.filename_long_enough()
+      && (self.filename_filter)(self.filename_edit.as_str())
+      && self.filename_error().is_none()
+      && match self.dialog_type {
+        DialogType::SelectFolder => true,
+        DialogType::OpenFile => self.passes_named_filter(&self.path.join(&self.filename_edit)),
+        DialogType::SaveFile => self.passes_named_filter(&self.save_target()),
+      }
+  }
+
+  /// Path the typed filename saves to, with the default extension.
+  fn save_target(&self) -> PathBuf {
+    self.with_default_extension(&self.path.join(&self.filename_edit))
+  }
+
+  /// Hint naming the expected file type if the typed filename doesn't match it, see
+  /// `enforce_filter_on_save`.
+  fn save_filter_error(&self) -> Option<String> {
+    let enforce = self.enforce_filter_on_save && self.dialog_type == DialogType::SaveFile;
+    if !enforce || self.filename_edit.is_empty() {
+      return None;
+    }
+
+    let path = self.save_target();
+    if (self.show_files_filter)(&path) && self.passes_named_filter(&path) {
+      return None;
+    }
+
+    let expected = match (
+      self.named_filters.get(self.named_filter),
+      &self.default_extension,
+    ) {
+      (Some((name, _)), _) => Some(name.clone()),
+      (None, Some(ext)) => Some(format!(".{ext}")),
+      (None, None) => None,
+    };
+
+    Some(match expected {
+      Some(expected) => format!("{}: {expected}", self.expected_type_text),
+      None => self.expected_type_text.to_string(),
+    })
+  }
+
+  /// Hint if the typed filename is in a folder outside of the root, or in one that doesn't exist
+  /// and won't be created, see `create_missing_dirs`.
+  fn save_folder_error(&self) -> Option<String> {
+    let save = self.dialog_type == DialogType::SaveFile;
+    if !save || self.filename_edit.is_empty() {
+      return None;
+    }
+
+    let target = self.save_target();
+    let parent = target.parent()?;
+    if !self.within_root(parent) {
+      return Some(self.outside_root_text.to_string());
+    }
+
+    match self.create_missing_dirs || parent.is_dir() {
+      true => None,
+      false => Some(self.missing_folder_text.to_string()),
+    }
+  }
+
+  /// Returns `true` if the typed filename is at least `filename_filter_min_len` long.
+  fn filename_long_enough(&self) -> bool {
+    self.filename_edit.chars().count() >= self.filename_filter_min_len
+  }
+
+  /// Message from the filename validator if it rejects the typed filename.
+  fn filename_error(&self) -> Option<String> {
+    if self.filename_edit.is_empty() || !self.filename_long_enough() {
+      return None;
+    }
+
+    let validator = self.filename_validator.as_ref()?;
+    validator(&self.filename_edit).err()
+  }
+
+  /// Returns `true` if the path passes the selected named filter, or there are none.
+  fn passes_named_filter(&self, path: &Path) -> bool {
+    match self.named_filters.get(self.named_filter) {
+      Some((_, filter)) => filter(path),
+      None => true,
+    }
+  }
+
+  /// Existing file named by the filename field, if it isn't already the selected file.
+  fn typed_file(&self) -> Option<FileInfo> {
+    if self.multi_select_enabled || self.filename_edit.is_empty() {
+      return None;
+    }
+
+    let path = self.path.join(&self.filename_edit);
+    if self
+      .selected_file
+      .as_ref()
+      .is_some_and(|info| info.path == path)
+    {
+      return None;
+    }
+
+    Some(FileInfo::new(path)).filter(|info| info.is_file())
+  }
+
+  fn can_save(&self) -> bool {
+    !self.filename_edit.is_empty()
+      && self.filename_accepted()
+      && self.save_filter_error().is_none()
+      && self.save_folder_error().is_none()
+      && self.save_target_writable()
+      && (self.allow_new_files || self.save_target_exists())
+  }
+
+  fn save_target_exists(&self) -> bool {
+    self.save_target().is_file()
+  }
+
+  /// Returns `false` if the file to save to, or the folder for a new one, is read-only.
+  fn save_target_writable(&self) -> bool {
+    self
+      .save_writable
+      .as_ref()
+      .map_or(true, |(_, writable)| *writable)
+  }
+
+  /// Read whether the save target can be written if it changed since the last frame.
+  fn update_save_writable(&mut self) {
+    if self.dialog_type != DialogType::SaveFile {
+      return;
+    }
+
+    let target = self.save_target();
+    if matches!(&self.save_writable, Some((path, _)) if *path == target) {
+      return;
+    }
+
+    let writable = match fs::metadata(&target) {
+      Ok(meta) => get_writable(&meta),
+      Err(_) => target
+        .parent()
+        .and_then(|parent| fs::metadata(parent).ok())
+        .and_then(|meta| get_writable(&meta)),
+    };
+    self.save_writable = Some((target, writable != Some(false)));
+  }
+
+  /// Path with the default extension added when it has none, or replaced when it's forced.
+  fn with_default_extension(&self, path: &Path) -> PathBuf {
+    let Some(ext) = &self.default_extension else {
+      return path.to_path_buf();
+    };
+
+    // A purely numeric suffix such as the "2" in "v1.2" isn't an extension.
+    let current = path
+      .extension()
+      .and_then(|current| current.to_str())
+      .filter(|current| !current.chars().all(|c| c.is_ascii_digit()));
+
+    match current {
+      Some(current) if !self.force_extension || current.eq_ignore_ascii_case(ext) => {
+        path.to_path_buf()
+      }
+      Some(_) => path.with_extension(ext),
+      None => {
+        let mut path = path.as_os_str().to_os_string();
+        path.push(".");
+        path.push(ext);
+        path.into()
+      }
+    }
+  }
+
+  fn can_open(&self) -> bool {
+    if self.multi_select_enabled {
+      if let Ok(files) = &self.files {
+        for file in files {
+          let name = get_file_name(file);
+          let valid = match &self.filename_validator {
+            Some(validator) => validator(name).is_ok(),
+            None => true,
+          };
+
+          if file.selected && (self.filename_filter)(name) && valid {
+            return true;
+          }
+        }
+      }
+      false
+    } else {
+      !self.filename_edit.is_empty() && self.filename_accepted()
+    }
+  }
+
+  /// Selected files and folders, from the list in multi-select mode.
+  fn selected_infos(&self) -> Vec<&FileInfo> {
+    if self.multi_select_enabled {
+      match &self.files {
+        Ok(files) => files.iter().filter(|info| info.selected).collect(),
+        Err(_) => Vec::new(),
+      }
+    } else {
+      self.selected_file.iter().collect()
+    }
+  }
+
+  fn files_to_copy(&self) -> Vec<PathBuf> {
+    self
+      .selected_infos()
+      .into_iter()
+      .filter(|info| info.is_file())
+      .map(|info| info.path.clone())
+      .collect()
+  }
+
+  fn files_to_delete(&self) -> Vec<PathBuf> {
+    self
+      .selected_infos()
+      .into_iter()
+      .filter(|info| self.can_delete(info))
+      .map(|info| info.path.clone())
+      .collect()
+  }
+
+  /// Returns `true` if the file exists in the current folder and isn't read-only. Drive roots
+  /// are never deleted.
+  fn can_delete(&self, info: &FileInfo) -> bool {
+    #[cfg(windows)]
+    if is_drive_root(&info.path) {
+      return false;
+    }
+
+    (info.file_type.is_some() || info.broken_link)
+      && info.writable != Some(false)
+      && info.path.parent() == Some(self.path.as_path())
+  }
+
+  /// Returns `true` if files can be changed in the current folder, see `can_create_in`.
+  fn can_modify(&self) -> bool {
+    match &self.can_create_in {
+      Some(can_create_in) => can_create_in(&self.path),
+      None => true,
+    }
+  }
+
+  fn can_rename(&self) -> bool {
+    if !self.filename_edit.is_empty() {
+      if let Some(file) = &self.selected_file {
+        return get_file_name(file) != self.filename_edit && file.writable != Some(false);
+      }
+    }
+    false
+  }
+
+  /// Shows the dialog if it is open. It is also responsible for state management.
+  /// Should be called every ui update.
+  pub fn show(&mut self, ctx: &Context) -> &Self {
+    let window = self.window();
+    self.show_custom(ctx, window)
+  }
+
+  /// Shows the dialog in the given window if it is open, like `show`. Use this to set the
+  /// window's id, frame, constraints and so on. The dialog sets the window's `open` flag itself,
+  /// and the builder methods that configure the window, such as `title` and `anchor`, are ignored.
+  pub fn show_custom(&mut self, ctx: &Context, window: Window) -> &Self {
+    self.expire_just_selected();
+    self.state = match self.state {
+      State::Open => {
+        let escape = ctx.input(|state| state.key_pressed(Key::Escape));
+        if self.close_on_escape && escape && self.creating_folder.is_none() {
+          self.state = State::Cancelled;
+          self.close_reason = Some(CloseReason::Escape);
+        }
+
+        let mut is_open = true;
+        self.ui(ctx, window, &mut is_open);
+        match is_open {
+          true => self.state,
+          false => {
+            self.close_reason = Some(CloseReason::WindowClose);
+            State::Cancelled
+          }
+        }
+      }
+      _ => State::Closed,
+    };
+
+    self.just_selected_shown = self.just_selected;
+    self
+  }
+
+  /// Shows the dialog inside the given `Ui` instead of a window if it is open, like `show`. Use
+  /// this to embed the dialog in a panel or another layout. The builder methods that configure the
+  /// window, such as `title` and `anchor`, are ignored.
+  pub fn show_inside(&mut self, ui: &mut Ui) -> &Self {
+    self.expire_just_selected();
+    self.state = match self.state {
+      State::Open => {
+        let escape = ui.input(|state| state.key_pressed(Key::Escape));
+        if self.close_on_escape && escape && self.creating_folder.is_none() {
+          self.state = State::Cancelled;
+          self.close_reason = Some(CloseReason::Escape);
+        }
+
+        self.ui_in_window(ui);
+        self.state
+      }
+      _ => State::Closed,
+    };
+
+    self.just_selected_shown = self.just_selected;
+    self
+  }
+
+  /// Clear `just_selected` once a `show` call has returned with it set.
+  fn expire_just_selected(&mut self) {
+    if self.just_selected_shown {
+      self.just_selected = false;
+      self.just_selected_shown = false;
+    }
+  }
+
+  /// Window configured by the builder methods.
+  fn window(&self) -> Window<'static> {
+    let mut window = Window::new(RichText::new(self.title.as_ref()).strong())
+      .default_size(self.default_size)
+      .resizable(self.resizable)
+      .collapsible(false);
+
+    if let Some(id) = self.id {
+      window = window.id(id);
+    }
+
+    if let Some((align, offset)) = self.anchor {
+      window = window.anchor(align, offset);
+    }
+
+    if let Some(current_pos) = self.current_pos {
+      window = window.current_pos(current_pos);
+    }
+
+    if let Some(default_pos) = self.default_pos {
+      window = window.default_pos(default_pos);
+    }
+
+    window
+  }
+
+  fn ui<'a>(&mut self, ctx: &Context, window: Window<'a>, is_open: &'a mut bool) {
+    let response = window.open(is_open).show(ctx, |ui| {
+      if self.keep_on_top {
+        ui.ctx().move_to_top(ui.layer_id());
+      }
+      self.content_size = Some(ui.max_rect().size());
+      self.ui_in_window(ui)
+    });
+
+    if let Some(response) = response {
+      let rect = response.response.rect;
+      if self.window_rect != Some(rect) {
+        self.window_rect = Some(rect);
+        if let Some(on_geometry_change) = &mut self.on_geometry_change {
+          on_geometry_change(rect);
+        }
+      }
+    }
+  }
+
+  fn ui_in_window(&mut self, ui: &mut Ui) {
+    enum Command {
+      Cancel,
+      CancelLoading,
+      NewFolder,
+      CreateDirectory(String),
+      CreateFile,
+      Folder,
+      Open(FileInfo),
+      OpenTyped(String),
+      OpenSelected,
+      BrowseDirectory(FileInfo),
+      SingleClickOpen(FileInfo),
+      Refresh,
+      Rename(PathBuf, PathBuf),
+      StartRename(usize),
+      Copy(Vec<PathBuf>),
+      Paste,
+      Save(FileInfo),
+      Accept,
+      Decline,
+      Delete(Vec<PathBuf>),
+      Select(usize),
+      MultiSelectRange(usize),
+      MultiSelect(usize),
+      MultiSelectSwitch(usize),
+      SelectAll,
+      InvertSelection,
+      Sort(SortBy),
+      UpDirectory,
+      SetPath(PathBuf),
+      Back,
+      Forward,
+    }
+    let mut command: Option<Command> = None;
+    self.poll_loading(ui.ctx());
+    self.poll_refresh(ui.ctx());
+    self.update_save_writable();
+
+    // Changing the filename while asked to overwrite drops the question for the old file.
+    if let Some(Prompt::Overwrite(info)) = &self.prompt {
+      if info.path != self.save_target() {
+        self.prompt = None;
+      }
+    }
+
+    // Enter while no text field has focus acts like double-clicking the selection. Like the Open
+    // button, it only confirms files that can be opened, and a typed filename comes first.
+    let enter_pressed = ui.input(|state| state.key_pressed(Key::Enter));
+    if self.confirm_on_enter && enter_pressed && ui.memory(|mem| mem.focused().is_none()) {
+      let browse = self
+        .selected_file
+        .as_ref()
+        .is_some_and(|info| info.is_dir());
+      let typed = match self.dialog_type {
+        DialogType::OpenFile => self.typed_file().filter(|_| self.filename_accepted()),
+        _ => None,
+      };
+      if let Some(info) = typed {
+        command = Some(Command::Open(info));
+      } else if browse || self.can_open() {
+        command = Some(Command::OpenSelected);
+      }
+    }
+
+    // Ctrl+A while no text field has focus selects all files.
+    if self.multi_select_enabled && ui.memory(|mem| mem.focused().is_none()) {
+      let select_all = ui.input_mut(|state| state.consume_key(Modifiers::COMMAND, Key::A));
+      if select_all {
+        command = Some(Command::SelectAll);
+      }
+    }
+
+    // Backspace while no text field has focus goes up a folder.
+    if ui.memory(|mem| mem.focused().is_none()) {
+      let up = ui.input_mut(|state| state.consume_key(Modifiers::NONE, Key::Backspace));
+      let parent = self.path.parent();
+      if up && parent.is_some_and(|parent| self.can_enter(parent)) {
+        command = Some(Command::UpDirectory);
+      }
+    }
+
+    // Ctrl+L moves the keyboard focus to the path field.
+    let focus_path = ui.input_mut(|state| state.consume_key(Modifiers::COMMAND, Key::L));
+
+    // Top directory field with buttons.
+    egui::TopBottomPanel::top("egui_file_top").show_inside(ui, |ui| {
+      ui.horizontal(|ui| {
+        ui.add_enabled_ui(!self.back_stack.is_empty(), |ui| {
+          let response = ui
+            .button("⬅")
+            .on_hover_text(self.back_button_hover_text.as_ref());
+          if response.clicked() {
+            command = Some(Command::Back);
+          }
+        });
+        ui.add_enabled_ui(!self.forward_stack.is_empty(), |ui| {
+          let response = ui
+            .button("➡")
+            .on_hover_text(self.forward_button_hover_text.as_ref());
+          if response.clicked() {
+            command = Some(Command::Forward);
+          }
+        });
+        let parent = self.path.parent();
+        ui.add_enabled_ui(parent.is_some_and(|parent| self.can_enter(parent)), |ui| {
+          let response = ui
+            .button("⬆")
+            .on_hover_text(self.parent_folder_button_hover_text.as_ref());
+          if response.clicked() {
+            command = Some(Command::UpDirectory);
+          }
+        });
+
+        if self.home_button {
+          let home = self.home_directory.clone().or_else(get_home_dir);
+          ui.add_enabled_ui(home.is_some(), |ui| {
+            let response = ui
+              .button("🏠")
+              .on_hover_text(self.home_button_hover_text.as_ref());
+            if let (true, Some(home)) = (response.clicked(), home) {
+              command = Some(Command::SetPath(home));
+            }
+          });
+        }
+
+        if self.recent_capacity > 0 {
+          let recent: Vec<&PathBuf> = self
+            .recent
+            .iter()
+            .filter(|path| **path != self.path)
+            .collect();
+          ui.add_enabled_ui(!recent.is_empty(), |ui| {
+            let response = ui.menu_button("🕘", |ui| {
+              for path in recent {
+                if ui.button(path.to_string_lossy()).clicked() {
+                  command = Some(Command::SetPath(path.clone()));
+                  ui.close_menu();
+                }
+              }
+            });
+            response
+              .response
+              .on_hover_text(self.recent_button_hover_text.as_ref());
+          });
+        }
+        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+          let response = ui
+            .button("⟲")
+            .on_hover_text(self.refresh_button_hover_text.as_ref());
+          if response.clicked() {
+            command = Some(Command::Refresh);
+          }
+
+          // Right to left, so the grid button comes first.
+          if self.view_selector {
+            let grid = self.view_mode == ViewMode::Grid;
+            ui.selectable_value(&mut self.view_mode, ViewMode::Grid, "⊞")
+              .on_hover_text(self.grid_view_hover_text.as_ref());
+            ui.selectable_value(&mut self.view_mode, ViewMode::List, "☰")
+              .on_hover_text(self.list_view_hover_text.as_ref());
+            if grid != (self.view_mode == ViewMode::Grid) {
+              self.scroll_to_focused = true;
+            }
+          }
+
+          if self.search_field {
+            let hint_text = self.search_hint_text.as_ref();
+            let text_edit = TextEdit::singleline(&mut self.search)
+              .hint_text(hint_text)
+              .desired_width(120.0);
+            ui.add(text_edit);
+          }
+
+          let path_edit_id = ui.id().with("path_edit");
+          if focus_path {
+            self.editing_path = true;
+            ui.memory_mut(|mem| mem.request_focus(path_edit_id));
+          }
+
+          if self.breadcrumbs && !self.editing_path {
+            let size = field_size(ui, self.path_field_width);
+            ui.allocate_ui_with_layout(size, Layout::left_to_right(egui::Align::Center), |ui| {
+              // Clicking outside of the segments switches to editing the path.
+              let id = ui.id().with("breadcrumbs");
+              let background = ui.interact(ui.max_rect(), id, Sense::click());
+              if background.clicked() {
+                self.editing_path = true;
+                ui.memory_mut(|mem| mem.request_focus(path_edit_id));
+              }
+
+              // Long paths scroll, keeping the current folder in view.
+              ScrollArea::horizontal()
+                .stick_to_right(true)
+                .show(ui, |ui| {
+                  ui.horizontal(|ui| {
+                    // The root is canonical, so the path must be too for the comparison.
+                    let current = match &self.root {
+                      Some(_) => fs::canonicalize(&self.path).unwrap_or_else(|_| self.path.clone()),
+                      None => self.path.clone(),
+                    };
+                    let mut ancestors: Vec<&Path> = current
+                      .ancestors()
+                      .take_while(|path| {
+                        self
+                          .root
+                          .as_ref()
+                          .map_or(true, |root| path.starts_with(root))
+                      })
+                      .collect();
+                    ancestors.reverse();
+                    for (index, path) in ancestors.into_iter().enumerate() {
+                      if index > 0 {
+                        ui.label(">");
+                      }
+
+                      // The drive root segment switches between drives.
+                      #[cfg(windows)]
+                      if is_drive_root(path) {
+                        let name = path.to_string_lossy();
+                        ui.menu_button(name.trim_end_matches('\\'), |ui| {
+                          for drive in get_drives() {
+                            if !(self.drive_filter)(&drive) {
+                              continue;
+                            }
+
+                            let text = self
+                              .drive_display
+                              .as_ref()
+                              .and_then(|display| display(&drive))
+                              .unwrap_or_else(|| drive.to_string_lossy().into());
+
+                            if ui.selectable_label(drive == path, text).clicked() {
+                              command = Some(Command::Open(FileInfo::new(drive)));
+                              ui.close_menu();
+                            }
+                          }
+                        });
+                        continue;
+                      }
+
+                      let name = self.get_path_display_name(path);
+
+                      if ui.selectable_label(path == current, name).clicked() {
+                        command = Some(Command::Open(FileInfo::new(path.to_path_buf())));
+                      }
+                    }
+                  });
+                });
+            });
+          } else {
+            // Tab takes the first completion instead of moving the focus.
+            let completing = !self.path_completions.is_empty();
+            let response = ui.add_sized(
+              field_size(ui, self.path_field_width),
+              TextEdit::singleline(&mut self.path_edit)
+                .id(path_edit_id)
+                .lock_focus(completing),
+            );
+
+            // Select the whole path so that typing replaces it.
+            if response.gained_focus() && !completing {
+              let len = self.path_edit.chars().count();
+              select_text(ui.ctx(), path_edit_id, 0, len);
+            }
+
+            // Complete the last segment once typing pauses, so slow drives aren't read per key.
+            let time = ui.input(|state| state.time);
+            if response.changed() {
+              self.path_edited_at = Some(time);
+            }
+
+            if let Some(edited_at) = self.path_edited_at.filter(|_| response.has_focus()) {
+              let wait = edited_at + PATH_COMPLETION_DELAY - time;
+              if wait > 0.0 {
+                ui.ctx().request_repaint_after_secs(wait as f32);
+              } else {
+                self.path_completions = self.read_path_completions();
+                self.path_edited_at = None;
+              }
+            }
+
+            let mut completion = None;
+            let tab = |state: &mut egui::InputState| state.consume_key(Modifiers::NONE, Key::Tab);
+            if completing && response.has_focus() && ui.input_mut(tab) {
+              completion = self.path_completions.first().cloned();
+            }
+
+            let completions_id = path_edit_id.with("completions");
+            if response.lost_focus() {
+              // Clicking a completion takes the focus from the field, but shouldn't open the path.
+              let pos = ui.input(|state| state.pointer.interact_pos());
+              let rect = ui.ctx().memory(|mem| mem.area_rect(completions_id));
+              if completing && pos.zip(rect).is_some_and(|(pos, rect)| rect.contains(pos)) {
+                ui.memory_mut(|mem| mem.request_focus(path_edit_id));
+              } else {
+                self.editing_path = false;
+                self.path_completions.clear();
+                self.path_edited_at = None;
+
+                // Only Enter opens the typed path, like the filename field. Otherwise, such as
+                // when tabbing or clicking away, the typed path is discarded.
+                if ui.input(|state| state.key_pressed(Key::Enter)) {
+                  command = Some(Command::OpenTyped(self.path_edit.clone()));
+                } else {
+                  self.path_edit = self.path.to_string_lossy().into();
+                }
+              }
+            }
+
+            if !self.path_completions.is_empty() {
+              egui::Area::new(completions_id)
+                .order(egui::Order::Foreground)
+                .fixed_pos(response.rect.left_bottom())
+                .show(ui.ctx(), |ui| {
+                  egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(response.rect.width());
+                    ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                      for name in &self.path_completions {
+                        if ui.selectable_label(false, name).clicked() {
+                          completion = Some(name.clone());
+                        }
+                      }
+                    });
+                  });
+                });
+            }
+
+            if let Some(name) = completion {
+              if let Some((dir, _)) = split_path_edit(&self.path_edit) {
+                let mut path = dir
+                  .join(name)
+                  .into_os_string()
+                  .into_string()
+                  .unwrap_or_default();
+                path.push(MAIN_SEPARATOR);
+                self.path_edit = path;
+                self.path_completions = self.read_path_completions();
+                self.path_edited_at = None;
+
+                let len = self.path_edit.chars().count();
+                select_text(ui.ctx(), path_edit_id, len, len);
+                ui.memory_mut(|mem| mem.request_focus(path_edit_id));
+              }
+            }
+          }
+        });
+      });
+      ui.add_space(ui.spacing().item_spacing.y);
+
+      if self.filter_bar {
+        ui.horizontal(|ui| {
+          ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.add_enabled_ui(!self.filter_text.is_empty(), |ui| {
+              if ui.button("✖").clicked() {
+                self.filter_text.clear();
+              }
+            });
+
+            let hint_text = self.filter_hint_text.as_ref();
+            let text_edit = TextEdit::singleline(&mut self.filter_text).hint_text(hint_text);
+            ui.add_sized(ui.available_size(), text_edit);
+          });
+        });
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      if let Some(header) = &mut self.header {
+        header(ui);
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+    });
+    self.update_name_filters();
+
+    // Bottom file field.
+    egui::TopBottomPanel::bottom("egui_file_bottom").show_inside(ui, |ui| {
+      ui.add_space(ui.spacing().item_spacing.y * 2.0);
+      ui.horizontal(|ui| {
+        let filename_field = self.filename_field_shown();
+        if filename_field {
+          ui.label(self.file_label_text.as_ref());
+        }
+
+        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+          let modify = self.can_modify();
+          let read_only = |response: Response| match modify {
+            true => response,
+            false => response.on_disabled_hover_text(self.read_only_folder_text.as_ref()),
+          };
+
+          if self.new_folder {
+            // The name is entered above the list, which isn't shown while loading or on an error.
+            let listed = self.loading.is_none() && self.files.is_ok();
+            let button = egui::Button::new(self.new_folder_button_text.as_ref());
+            if read_only(ui.add_enabled(modify && listed, button)).clicked() {
+              command = Some(Command::NewFolder);
+            }
+          }
+
+          if self.new_file {
+            let button = egui::Button::new(self.new_file_button_text.as_ref());
+            if read_only(ui.add_enabled(modify, button)).clicked() {
+              command = Some(Command::CreateFile);
+            }
+          }
+
+          if self.copy_paste {
+            ui.add_enabled_ui(modify && !self.copied_files.is_empty(), |ui| {
+              if read_only(ui.button(self.paste_button_text.as_ref())).clicked() {
+                command = Some(Command::Paste);
+              }
+            });
+
+            let files = self.files_to_copy();
+            ui.add_enabled_ui(!files.is_empty(), |ui| {
+              if ui.button(self.copy_button_text.as_ref()).clicked() {
+                command = Some(Command::Copy(files));
+              }
+            });
+          }
+
+          if self.delete {
+            let paths = self.files_to_delete();
+            ui.add_enabled_ui(modify && !paths.is_empty(), |ui| {
+              if read_only(ui.button(self.delete_button_text.as_ref())).clicked() {
+                command = Some(Command::Delete(paths));
+              }
+            });
+          }
+
+          // Renaming takes the new name from the filename field.
+          if self.rename && filename_field {
+            ui.add_enabled_ui(modify && self.can_rename(), |ui| {
+              if read_only(ui.button(self.rename_button_text.as_ref())).clicked() {
+                if let Some(from) = self.selected_file.clone() {
+                  let to = from.path.with_file_name(&self.filename_edit);
+                  command = Some(Command::Rename(from.path, to));
+                }
+              }
+            });
+          }
+
+          if !filename_field {
+            return;
+          }
+
+          let response = ui.add_sized(
+            field_size(ui, self.filename_field_width),
+            TextEdit::singleline(&mut self.filename_edit),
+          );
+
+          // Select the name without its extension, ready to be typed over.
+          if std::mem::take(&mut self.focus_filename) {
+            response.request_focus();
+            let is_dir = self
+              .selected_file
+              .as_ref()
+              .is_some_and(|info| info.is_dir());
+            let name = Path::new(&self.filename_edit);
+            let stem = match is_dir {
+              true => None,
+              false => name.file_stem().and_then(|stem| stem.to_str()),
+            };
+            let len = stem.unwrap_or(&self.filename_edit).chars().count();
+            select_text(ui.ctx(), response.id, 0, len);
+          }
+
+          if response.lost_focus() {
+            let ctx = response.ctx;
+            let enter_pressed = ctx.input(|state| state.key_pressed(Key::Enter));
+
+            if self.confirm_on_enter && enter_pressed && self.filename_accepted() {
+              let path = self.path.join(&self.filename_edit);
+              match self.dialog_type {
+                DialogType::SelectFolder => command = Some(Command::Folder),
+                DialogType::OpenFile => {
+                  if path.exists() {
+                    command = Some(Command::Open(FileInfo::new(path)));
+                  } else if let Some(info) =
+                    self.selected_file.as_ref().filter(|info| info.is_dir())
+                  {
+                    command = Some(Command::BrowseDirectory(info.clone()));
+                  }
+                }
+                DialogType::SaveFile => {
+                  let file_info = FileInfo::new(path);
+                  if file_info.is_dir() {
+                    command = Some(Command::Open(file_info));
+                  } else if self.can_save() {
+                    command = Some(Command::Save(file_info));
+                  }
+                }
+              }
+            }
+          }
+        });
+      });
+
+      // Names shorter than the minimum aren't checked yet, so they show no error.
+      if self.filename_field_shown() && self.filename_long_enough() {
+        let msg = self
+          .filename_error()
+          .or_else(|| self.save_filter_error())
+          .or_else(|| self.save_folder_error());
+        if let Some(msg) = msg {
+          let text = RichText::new(msg)
+            .small()
+            .color(ui.visuals().error_fg_color);
+          ui.label(text);
+        }
+      }
+
+      ui.add_space(ui.spacing().item_spacing.y);
+
+      if !self.named_filters.is_empty() {
+        ui.horizontal(|ui| {
+          ui.label(self.file_type_label_text.as_ref());
+
+          let selected_text = &self.named_filters[self.named_filter].0;
+          let mut selected = self.named_filter;
+          egui::ComboBox::from_id_salt("egui_file_filter")
+            .selected_text(selected_text.as_str())
+            .show_ui(ui, |ui| {
+              for (idx, (name, _)) in self.named_filters.iter().enumerate() {
+                ui.selectable_value(&mut selected, idx, name.as_str());
+              }
+            });
+
+          if selected != self.named_filter {
+            self.named_filter = selected;
+            command = Some(Command::Refresh);
+          }
+        });
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      if let Some(msg) = &self.rename_error {
+        ui.colored_label(ui.visuals().error_fg_color, msg);
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      if let Some(err) = &self.navigation_error {
+        let now = ui.input(|state| state.time);
+        let until = *self.navigation_error_until.get_or_insert(now + 3.0);
+        if until > now {
+          let color = ui.visuals().warn_fg_color;
+          ui.add(Label::new(RichText::new(format!("⚠ {err}")).color(color)).truncate());
+          ui.add_space(ui.spacing().item_spacing.y);
+          ui.ctx().request_repaint_after_secs((until - now) as f32);
+        } else {
+          self.navigation_error = None;
+          self.navigation_error_until = None;
+        }
+      }
+
+      if let Some(until) = self.location_changed_until {
+        let remaining = until - ui.input(|state| state.time);
+        if remaining > 0.0 {
+          ui.horizontal(|ui| {
+            let color = ui.visuals().warn_fg_color;
+            ui.colored_label(color, self.location_changed_text.as_ref());
+            ui.add(Label::new(self.path.to_string_lossy()).truncate());
+          });
+          ui.add_space(ui.spacing().item_spacing.y);
+          ui.ctx().request_repaint_after_secs(remaining as f32);
+        } else {
+          self.location_changed_until = None;
+        }
+      }
+
+      if self.selected_path {
+        if let Some(info) = &self.selected_file {
+          let path = info.path.to_string_lossy();
+          ui.add(Label::new(path.as_ref()).truncate())
+            .on_hover_text(path.as_ref());
+          ui.add_space(ui.spacing().item_spacing.y);
+        }
+      }
+
+      // Overwrite or delete confirmation.
+      if let Some(prompt) = &self.prompt {
+        ui.horizontal(|ui| {
+          let color = ui.visuals().warn_fg_color;
+          let (text, name) = match prompt {
+            Prompt::Overwrite(info) => (&self.overwrite_text, self.get_display_name(info)),
+            Prompt::Rename(_, to) => (&self.overwrite_text, self.get_path_display_name(to)),
+            Prompt::Delete(paths) => (&self.delete_text, self.get_paths_label(paths)),
+          };
+          ui.colored_label(color, text.as_ref());
+          ui.add(Label::new(name.as_ref()).truncate());
+        });
+
+        ui.horizontal(|ui| {
+          if ui.button(self.yes_button_text.as_ref()).clicked() {
+            command = Some(Command::Accept);
+          }
+
+          if ui.button(self.no_button_text.as_ref()).clicked() {
+            command = Some(Command::Decline);
+          }
+        });
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      if self.status_bar {
+        match &self.files {
+          Ok(files) if files.is_empty() => {
+            ui.label(self.empty_folder_text.as_ref());
+          }
+          Ok(files) => {
+            let shown = files
+              .iter()
+              .filter(|info| self.is_shown(get_file_name(info)))
+              .count();
+            let selected = match self.multi_select_enabled {
+              true => self.selection_absolute().len(),
+              false => usize::from(self.selected_file.is_some()),
+            };
+
+            let mut text = format!("{shown} {}", self.items_text);
+            if selected > 0 {
+              text += &format!(", {selected} {}", self.selected_text);
+            }
+            ui.label(text);
+          }
+          Err(err) => {
+            if self.error_ui(ui, err) {
+              command = Some(Command::Refresh);
+            }
+          }
+        }
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      // Confirm, Cancel buttons.
+      ui.horizontal(|ui| {
+        match self.dialog_type {
+          DialogType::SelectFolder => {
+            ui.horizontal(|ui| {
               if ui.button(self.open_button_text.as_ref()).clicked() {
                 command = Some(Command::Folder);
               };
             });
           }
-          DialogType::OpenFile => {
+          DialogType::OpenFile => {
+            ui.horizontal(|ui| {
+              if !self.can_open() {
+                ui.disable();
+              }
+
+              if ui.button(self.open_button_text.as_ref()).clicked() {
+                // A typed filename takes precedence over the list selection, even if the list
+                // filter hides that file. Only the filename filter applies to it.
+                command = match self.typed_file() {
+                  Some(info) => Some(Command::Open(info)),
+                  None => Some(Command::OpenSelected),
+                };
+              };
+            });
+          }
+          DialogType::SaveFile => {
+            let should_open_directory = match &self.selected_file {
+              Some(file) => file.is_dir(),
+              None => false,
+            };
+
+            if should_open_directory {
+              if ui.button(self.open_button_text.as_ref()).clicked() {
+                command = Some(Command::OpenSelected);
+              };
+            } else {
+              ui.horizontal(|ui| {
+                if !self.can_save() {
+                  ui.disable();
+                }
+
+                let mut response = ui.button(self.save_button_text.as_ref());
+                if !self.allow_new_files && !self.save_target_exists() {
+                  response =
+                    response.on_disabled_hover_text(self.existing_file_hover_text.as_ref());
+                } else if !self.save_target_writable() {
+                  response = response.on_disabled_hover_text(self.read_only_text.as_ref());
+                }
+
+                if response.clicked() {
+                  let filename = &self.filename_edit;
+                  let path = self.path.join(filename);
+                  command = Some(Command::Save(FileInfo::new(path)));
+                };
+              });
+            }
+          }
+        }
+
+        if ui.button(self.cancel_button_text.as_ref()).clicked() {
+          command = Some(Command::Cancel);
+        }
+
+        if self.multi_select_enabled {
+          ui.separator();
+          if ui.button(self.select_all_button_text.as_ref()).clicked() {
+            command = Some(Command::SelectAll);
+          }
+
+          if ui
+            .button(self.invert_selection_button_text.as_ref())
+            .clicked()
+          {
+            command = Some(Command::InvertSelection);
+          }
+        }
+
+        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+          if ui
+            .checkbox(
+              &mut self.show_hidden,
+              self.show_hidden_checkbox_text.as_ref(),
+            )
+            .changed()
+          {
+            self.refresh();
+          }
+        });
+      });
+    });
+
+    // Bookmarks and drives.
+    if self.sidebar {
+      egui::SidePanel::left("egui_file_sidebar")
+        .resizable(true)
+        .show_inside(ui, |ui| {
+          ScrollArea::vertical().show(ui, |ui| {
+            ui.with_layout(ui.layout().with_cross_justify(true), |ui| {
+              for (label, path) in &self.bookmarks {
+                let selected = self.same_path(&self.path, path);
+                let response = ui
+                  .selectable_label(selected, label)
+                  .on_hover_text(path.to_string_lossy());
+                if response.clicked() {
+                  command = Some(Command::SetPath(path.clone()));
+                }
+              }
+
+              #[cfg(windows)]
+              {
+                let drives: Vec<PathBuf> = get_drives()
+                  .into_iter()
+                  .filter(|drive| (self.drive_filter)(drive))
+                  .collect();
+
+                if !self.bookmarks.is_empty() && !drives.is_empty() {
+                  ui.separator();
+                }
+
+                for drive in drives {
+                  let text = self
+                    .drive_display
+                    .as_ref()
+                    .and_then(|display| display(&drive))
+                    .unwrap_or_else(|| drive.to_string_lossy().into());
+
+                  if ui.selectable_label(drive == self.path, text).clicked() {
+                    command = Some(Command::SetPath(drive));
+                  }
+                }
+              }
+            });
+          });
+        });
+    }
+
+    // Preview of the selected file.
+    if self.preview.is_some() {
+      self.update_preview();
+      egui::SidePanel::right("egui_file_preview")
+        .resizable(true)
+        .show_inside(ui, |ui| {
+          if let (Some(preview), Some((_, content))) = (&mut self.preview, &self.preview_content) {
+            match content {
+              Ok(bytes) => preview(bytes, ui),
+              Err(err) => {
+                ui.colored_label(ui.visuals().error_fg_color, err.as_str());
+              }
+            }
+          }
+        });
+    }
+
+    // File list.
+    egui::CentralPanel::default().show_inside(ui, |ui| {
+      // Indices of the files that pass the filter bar and search field.
+      let rows: Vec<usize> = match &self.files {
+        Ok(files) => files
+          .iter()
+          .enumerate()
+          .filter(|(_, info)| self.is_shown(get_file_name(info)))
+          .map(|(idx, _)| idx)
+          .collect(),
+        Err(_) => Vec::new(),
+      };
+
+      if self.loading.is_some() {
+        ui.horizontal(|ui| {
+          ui.spinner();
+          if ui.button(self.cancel_button_text.as_ref()).clicked() {
+            command = Some(Command::CancelLoading);
+          }
+        });
+        return;
+      }
+
+      // The status bar shows the error instead when it's enabled.
+      if let Err(err) = &self.files {
+        if !self.status_bar && self.error_ui(ui, err) {
+          command = Some(Command::Refresh);
+        }
+        return;
+      }
+
+      // Name for New Folder, created on Enter and dropped on Escape or clicking elsewhere.
+      if let Some(name) = &mut self.creating_folder {
+        let response = ui
+          .horizontal(|ui| {
+            ui.label("🗀");
+            ui.add(TextEdit::singleline(name).desired_width(f32::INFINITY))
+          })
+          .inner;
+
+        if std::mem::take(&mut self.focus_new_folder) {
+          response.request_focus();
+          select_text(ui.ctx(), response.id, 0, name.chars().count());
+        }
+
+        if response.lost_focus() {
+          let enter_pressed = ui.input(|state| state.key_pressed(Key::Enter));
+          if enter_pressed && !name.is_empty() {
+            command = Some(Command::CreateDirectory(name.clone()));
+          }
+          self.creating_folder = None;
+        }
+        ui.add_space(ui.spacing().item_spacing.y);
+      }
+
+      if self.files.is_ok() && rows.is_empty() {
+        match &mut self.empty_view {
+          Some(view) => view(ui),
+          None => {
+            let text = match self.files.as_ref().is_ok_and(|files| files.is_empty()) {
+              true => self.empty_folder_text.as_ref(),
+              false => self.no_matches_text.as_ref(),
+            };
+            ui.label(text);
+          }
+        }
+        return;
+      }
+
+      if self.sort_header {
+        ui.horizontal(|ui| {
+          let columns = [
+            (SortBy::Name, self.name_column_text.as_ref()),
+            (SortBy::Size, self.size_column_text.as_ref()),
+            (SortBy::Modified, self.modified_column_text.as_ref()),
+            (SortBy::Type, self.type_column_text.as_ref()),
+          ];
+
+          for (sort_by, text) in columns {
+            let text = match (sort_by == self.sort_by, self.sort_order) {
+              (true, SortOrder::Ascending) => format!("{text} ⬆"),
+              (true, SortOrder::Descending) => format!("{text} ⬇"),
+              (false, _) => text.to_string(),
+            };
+
+            if ui.selectable_label(sort_by == self.sort_by, text).clicked() {
+              command = Some(Command::Sort(sort_by));
+            }
+          }
+        });
+        ui.separator();
+      }
+
+      // Only a custom height changes the rows, so the default layout stays as it was.
+      let custom_height = self.row_height.or_else(|| match self.density {
+        Density::Compact => None,
+        Density::Comfortable => Some(ui.text_style_height(&egui::TextStyle::Body) * 1.75),
+      });
+      let row_height = match custom_height {
+        Some(height) => height,
+        None => ui.text_style_height(&egui::TextStyle::Body),
+      };
+      // The grid shows as many cells per line as fit.
+      let grid = self.view_mode == ViewMode::Grid;
+      let (line_height, columns) = match grid {
+        true => {
+          let spacing = ui.spacing().item_spacing.x;
+          let width = ui.available_width() - ui.spacing().scroll.allocated_width();
+          let columns = ((width + spacing) / (GRID_CELL_SIZE.x + spacing)).floor();
+          (GRID_CELL_SIZE.y, (columns as usize).max(1))
+        }
+        false => (row_height, 1),
+      };
+      let date_width = self.details.then(|| {
+        let font = egui::TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let text = "0000-00-00 00:00".to_string();
+        ui.fonts(|f| f.layout_no_wrap(text, font, color)).size().x
+      });
+      let mut scroll_area = ScrollArea::vertical();
+      if let Some(offset) = self.pending_scroll.take() {
+        scroll_area = scroll_area.vertical_scroll_offset(offset);
+      }
+
+      // Center the focused file, e.g. after `reveal`.
+      if std::mem::take(&mut self.scroll_to_focused) {
+        let spaced_height = line_height + ui.spacing().item_spacing.y;
+        let focused = self.focused_index;
+        if let Some(row) = rows.iter().position(|&idx| Some(idx) == focused) {
+          let height = ui.available_height();
+          let offset = (row / columns) as f32 * spaced_height - (height - spaced_height) / 2.0;
+          scroll_area = scroll_area.vertical_scroll_offset(offset.max(0.0));
+        }
+      }
+
+      // Keyboard navigation while no text field has focus.
+      if ui.memory(|mem| mem.focused().is_none()) {
+        let spaced_height = line_height + ui.spacing().item_spacing.y;
+        let (offset, height) = self.list_viewport;
+        let page = ((height / spaced_height) as usize).max(1) * columns;
+        let row = self
+          .key_nav_row(ui, &rows, columns, page)
+          .or_else(|| self.type_jump_row(ui, &rows));
+        if let Some(row) = row {
+          command = Some(Command::Select(rows[row]));
+
+          // Scroll just enough to show the row.
+          let top = (row / columns) as f32 * spaced_height;
+          if top < offset {
+            scroll_area = scroll_area.vertical_scroll_offset(top);
+          } else if top + spaced_height > offset + height {
+            scroll_area = scroll_area.vertical_scroll_offset(top + spaced_height - height);
+          }
+        }
+      }
+
+      // Visible folders whose item counts haven't been read yet.
+      let mut uncounted = Vec::new();
+      let mut single_click_opened = self.single_click_opened;
+      let mut pointer_row = None;
+
+      // Dragging across rows selects them instead of scrolling.
+      if self.multi_select_enabled {
+        scroll_area = scroll_area.drag_to_scroll(false);
+      }
+
+      // Files shown this frame, with their responses to handle once the list is drawn.
+      let mut responses = Vec::new();
+      let mut unloaded = Vec::new();
+      let line_count = rows.len().div_ceil(columns);
+      let output = scroll_area.show_rows(ui, line_height, line_count, |ui, range| {
+        let Ok(files) = self.files.as_ref() else {
+          return;
+        };
+
+        let selected = self.selected_file.as_ref().map(|info| &info.path);
+        let is_selected = |info: &FileInfo| match self.multi_select_enabled {
+          true => info.selected,
+          false => selected.is_some_and(|path| self.same_path(&info.path, path)),
+        };
+
+        if grid {
+          for line in range {
+            let end = ((line + 1) * columns).min(rows.len());
             ui.horizontal(|ui| {
-              if !self.can_open() {
-                ui.disable();
-              }
+              for (row, &idx) in rows.iter().enumerate().take(end).skip(line * columns) {
+                let info = &files[idx];
+                let thumbnail = match self.thumbnails.get(&info.path) {
+                  Some(thumbnail) => thumbnail.as_ref(),
+                  None => {
+                    if self.thumbnail.is_some() && info.is_file() {
+                      unloaded.push(info.path.clone());
+                    }
+                    None
+                  }
+                };
 
-              if ui.button(self.open_button_text.as_ref()).clicked() {
-                command = Some(Command::OpenSelected);
-              };
+                let enabled = self.can_select(info);
+                let response = self.grid_cell(ui, info, thumbnail, is_selected(info), enabled);
+                if ui.rect_contains_pointer(response.rect) {
+                  pointer_row = Some(row);
+                }
+                responses.push((idx, response));
+              }
             });
           }
-          DialogType::SaveFile => {
-            let should_open_directory = match &self.selected_file {
-              Some(file) => file.is_dir(),
-              None => false,
-            };
+          return;
+        }
 
-            if should_open_directory {
-              if ui.button(self.open_button_text.as_ref()).clicked() {
-                command = Some(Command::OpenSelected);
-              };
-            } else {
-              ui.horizontal(|ui| {
-                if !self.can_save() {
-                  ui.disable();
-                }
+        ui.with_layout(ui.layout().with_cross_justify(true), |ui| {
+          // Selectable labels are at least as tall as the interact size.
+          if let Some(height) = custom_height {
+            ui.spacing_mut().interact_size.y = height;
+          }
 
-                if ui.button(self.save_button_text.as_ref()).clicked() {
-                  let filename = &self.filename_edit;
-                  let path = self.path.join(filename);
-                  command = Some(Command::Save(FileInfo::new(path)));
-                };
+          // Width left for the labels beside the details.
+          let spacing = ui.spacing().item_spacing.x;
+          let mut label_width = ui.available_width() - ui.spacing().button_padding.x * 2.0;
+          if let Some(date_width) = date_width {
+            label_width -= date_width + text_width(ui, "0000.0 KiB") + spacing * 3.0;
+          }
+
+          // Names never run under the details, so they're shortened even without truncation.
+          let truncate = match (self.truncate_names, date_width) {
+            (TruncateStyle::None, Some(_)) => TruncateStyle::End,
+            (style, _) => style,
+          };
+
+          for row in range {
+            let idx = rows[row];
+            let info = &files[idx];
+            let icon = self.get_icon(info);
+            let name = self.get_list_name(info);
+            let mut suffix = String::new();
+            if info.symlink && !info.broken_link {
+              suffix += " 🔗";
+            }
+            if info.writable == Some(false) {
+              suffix += " 🔒";
+            }
+
+            if self.folder_counts_enabled && info.is_dir() {
+              match self.folder_counts.get(&info.path) {
+                Some(Some(count)) => suffix += &format!(" ({count})"),
+                Some(None) => {}
+                None => uncounted.push(info.path.clone()),
+              }
+            }
+
+            let width = label_width - text_width(ui, &format!("{icon} {suffix}"));
+            let shown = truncate_text(ui, &name, width, truncate);
+            let truncated = matches!(shown, Cow::Owned(_));
+            let label = format!("{icon} {shown}{suffix}");
+
+            let enabled = self.can_select(info);
+            let stripe = ui.painter().add(Shape::Noop);
+            let mut response =
+              ui.add_enabled(enabled, SelectableLabel::new(is_selected(info), label));
+            if truncated {
+              response = response.on_hover_text(name.as_ref());
+            }
+            if info.symlink {
+              response = response.on_hover_ui(|ui| {
+                if let Ok(target) = fs::read_link(&info.path) {
+                  ui.label(format!("🔗 {}", target.display()));
+                }
               });
             }
+            if ui.rect_contains_pointer(response.rect) {
+              pointer_row = Some(row);
+            }
+
+            if self.striped && row % 2 == 1 {
+              let color = ui.visuals().faint_bg_color;
+              ui.painter()
+                .set(stripe, Shape::rect_filled(response.rect, 0.0, color));
+            }
+
+            // Size and modified date, right aligned over the row.
+            if let Some(date_width) = date_width {
+              let font = egui::TextStyle::Body.resolve(ui.style());
+              let color = ui.visuals().weak_text_color();
+              let spacing = ui.spacing().item_spacing.x;
+              let right = response.rect.right() - spacing;
+              let y = response.rect.center().y;
+              let date = info.modified.map(format_modified).unwrap_or_default();
+              let size = info.size.map(format_size).unwrap_or_default();
+              let painter = ui.painter();
+              painter.text(
+                Pos2::new(right, y),
+                Align2::RIGHT_CENTER,
+                date,
+                font.clone(),
+                color,
+              );
+              let right = right - date_width - spacing * 2.0;
+              painter.text(Pos2::new(right, y), Align2::RIGHT_CENTER, size, font, color);
+            }
+
+            responses.push((idx, response));
+          }
+        });
+      });
+      self.list_viewport = (output.state.offset.y, output.inner_rect.height());
+
+      for (idx, response) in responses {
+        let Ok(files) = &self.files else {
+          break;
+        };
+
+        let info = &files[idx];
+
+        // The built-in items use the same commands as the buttons.
+        response.context_menu(|ui| {
+          let modify = self.can_modify() && info.writable != Some(false);
+          let rename = self.rename && !self.multi_select_enabled && self.filename_field_shown();
+          let button = egui::Button::new(self.rename_button_text.as_ref());
+          if rename && ui.add_enabled(modify, button).clicked() {
+            command = Some(Command::StartRename(idx));
+            ui.close_menu();
+          }
+
+          let button = egui::Button::new(self.delete_button_text.as_ref());
+          let delete = modify && self.can_delete(info);
+          if self.delete && ui.add_enabled(delete, button).clicked() {
+            // A selected file is deleted along with the rest of the selection.
+            let paths = match self.multi_select_enabled && info.selected {
+              true => self.files_to_delete(),
+              false => vec![info.path.clone()],
+            };
+            command = Some(Command::Delete(paths));
+            ui.close_menu();
+          }
+
+          if ui.button(self.copy_path_text.as_ref()).clicked() {
+            ui.ctx().copy_text(info.path.to_string_lossy().into());
+            ui.close_menu();
+          }
+
+          if let Some(context_menu) = &self.context_menu {
+            ui.separator();
+            context_menu(&info.path, ui);
           }
+        });
+
+        // Greyed out files can't be clicked, selected or opened.
+        if !self.can_select(info) {
+          continue;
         }
 
-        if ui.button(self.cancel_button_text.as_ref()).clicked() {
-          command = Some(Command::Cancel);
+        // The second click of a double click lands in the folder the first click opened.
+        if response.clicked() && single_click_opened {
+          single_click_opened = false;
+          if response.double_clicked() {
+            continue;
+          }
         }
 
-        #[cfg(unix)]
-        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-          if ui
-            .checkbox(
-              &mut self.show_hidden,
-              self.show_hidden_checkbox_text.as_ref(),
-            )
-            .changed()
-          {
-            self.refresh();
+        let modifiers = ui.input(|i| i.modifiers);
+        let plain_click = !modifiers.shift && !modifiers.ctrl;
+        if response.clicked() && self.open_on_single_click && info.is_dir() && plain_click {
+          command = Some(Command::SingleClickOpen(info.clone()));
+        } else if response.clicked() {
+          if self.multi_select_enabled {
+            if ui.input(|i| i.modifiers.shift) {
+              command = Some(Command::MultiSelectRange(idx))
+            } else if ui.input(|i| i.modifiers.ctrl) {
+              command = Some(Command::MultiSelectSwitch(idx))
+            } else {
+              command = Some(Command::MultiSelect(idx))
+            }
+          } else {
+            command = Some(Command::Select(idx));
           }
-        });
-      });
+        }
+
+        if response.double_clicked() {
+          match self.dialog_type {
+            DialogType::SelectFolder => {
+              // Always open folder on double click, otherwise SelectFolder cant enter sub-folders.
+              command = Some(Command::OpenSelected);
+            }
+            // Open or save file only if name matches filter.
+            DialogType::OpenFile => {
+              if info.is_dir() {
+                command = Some(Command::BrowseDirectory(info.clone()));
+              } else if self.filename_accepted() {
+                command = Some(Command::Open(info.clone()));
+              }
+            }
+            DialogType::SaveFile => {
+              if info.is_dir() {
+                command = Some(Command::OpenSelected);
+              } else if self.filename_accepted() {
+                command = Some(Command::Save(info.clone()));
+              }
+            }
+          }
+        }
+      }
+      self.single_click_opened = single_click_opened;
+
+      if self.multi_select_enabled {
+        self.drag_select(ui, &rows, pointer_row, output.inner_rect);
+      }
+
+      // Only load a few thumbnails per frame so that scrolling stays smooth.
+      if let Some(thumbnail) = &mut self.thumbnail {
+        if unloaded.len() > MAX_THUMBNAILS_PER_FRAME {
+          ui.ctx().request_repaint();
+        }
+
+        for path in unloaded.into_iter().take(MAX_THUMBNAILS_PER_FRAME) {
+          let texture = thumbnail(&path, ui.ctx());
+          self.thumbnails.insert(path, texture);
+        }
+      }
+
+      // Only count a few folders per frame so that large folders don't stall the UI.
+      if uncounted.len() > MAX_FOLDER_COUNTS_PER_FRAME {
+        ui.ctx().request_repaint();
+      }
+
+      for path in uncounted.into_iter().take(MAX_FOLDER_COUNTS_PER_FRAME) {
+        let count = fs::read_dir(&path).ok().map(|entries| entries.count());
+        self.folder_counts.insert(path, count);
+      }
+
+      #[cfg(windows)]
+      self.update_drive_labels(ui.ctx());
     });
 
-    // File list.
-    egui::CentralPanel::default().show_inside(ui, |ui| {
-      ScrollArea::vertical().show_rows(
-        ui,
-        ui.text_style_height(&egui::TextStyle::Body),
-        self.files.as_ref().map_or(0, |files| files.len()),
-        |ui, range| match self.files.as_ref() {
-          Ok(files) => {
-            ui.with_layout(ui.layout().with_cross_justify(true), |ui| {
-              let selected = self.selected_file.as_ref().map(|info| &info.path);
-              let range_start = range.start;
-
-              for (n, info) in files[range].iter().enumerate() {
-                let idx = n + range_start;
-                let label = match info.is_dir() {
-                  true => "🗀 ",
-                  false => "🗋 ",
-                }
-                .to_string()
-                  + get_file_name(info);
+    if let Some(command) = command {
+      let prev_path = self.path.clone();
+      match command {
+        Command::Select(idx) => self.select_index(idx),
+        Command::MultiSelect(idx) => self.select_reset_multi(idx),
+        Command::MultiSelectRange(idx) => self.select_range(idx),
+        Command::MultiSelectSwitch(idx) => self.select_switch_multi(idx),
+        Command::SelectAll => self.select_all(),
+        Command::InvertSelection => self.invert_selection(),
+        Command::Folder => {
+          let path = self.get_folder().to_owned();
+          self.selected_file = Some(FileInfo::new(path));
+          self.confirm();
+        }
+        Command::Open(info) => {
+          self.select(Some(info));
+          self.open_selected();
+        }
+        Command::OpenTyped(text) => {
+          let mut info = FileInfo::new(PathBuf::from(&text));
+          let create = self.create_missing_dirs && self.dialog_type == DialogType::SaveFile;
+          if create && info.file_type.is_none() && !info.broken_link {
+            // A name with an extension is the file to save, unless it ends with a separator.
+            let path = info.path.clone();
+            let name = path.file_name().filter(|_| !text.ends_with(is_separator));
+            let (dir, name) = match (path.extension(), path.parent(), name) {
+              (Some(_), Some(parent), Some(name)) => (parent, Some(name)),
+              _ => (path.as_path(), None),
+            };
 
-                let is_selected = if self.multi_select_enabled {
-                  files[idx].selected
-                } else {
-                  Some(&info.path) == selected
-                };
-                let response = ui.selectable_label(is_selected, label);
-                if response.clicked() {
-                  if self.multi_select_enabled {
-                    if ui.input(|i| i.modifiers.shift) {
-                      command = Some(Command::MultiSelectRange(idx))
-                    } else if ui.input(|i| i.modifiers.ctrl) {
-                      command = Some(Command::MultiSelectSwitch(idx))
-                    } else {
-                      command = Some(Command::MultiSelect(idx))
-                    }
-                  } else {
-                    command = Some(Command::Select(info.clone()));
+            // Folders outside of the root or below the maximum depth are never created.
+            if self.within_root(dir) && self.can_browse(dir) {
+              match fs::create_dir_all(dir) {
+                Ok(_) => {
+                  info = FileInfo::new(dir.to_path_buf());
+                  if let Some(name) = name {
+                    self.filename_edit = name.to_string_lossy().into();
                   }
                 }
+                Err(err) => println!("Error while creating directory: {err}"),
+              }
+            }
+          }
 
-                if response.double_clicked() {
-                  match self.dialog_type {
-                    DialogType::SelectFolder => {
-                      // Always open folder on double click, otherwise SelectFolder cant enter sub-folders.
-                      command = Some(Command::OpenSelected);
-                    }
-                    // Open or save file only if name matches filter.
-                    DialogType::OpenFile => {
-                      if info.is_dir() {
-                        command = Some(Command::BrowseDirectory(info.clone()));
-                      } else if (self.filename_filter)(self.filename_edit.as_str()) {
-                        command = Some(Command::Open(info.clone()));
-                      }
-                    }
-                    DialogType::SaveFile => {
-                      if info.is_dir() {
-                        command = Some(Command::OpenSelected);
-                      } else if (self.filename_filter)(self.filename_edit.as_str()) {
-                        command = Some(Command::Save(info.clone()));
-                      }
-                    }
-                  }
-                }
+          self.select(Some(info));
+          self.open_selected();
+        }
+        Command::OpenSelected => self.open_selected(),
+        Command::BrowseDirectory(dir) => {
+          self.selected_file = Some(dir);
+          self.open_selected();
+        }
+        Command::SingleClickOpen(dir) => {
+          self.single_click_opened = true;
+          self.selected_file = Some(dir);
+          self.open_selected();
+        }
+        Command::Save(file) => {
+          self.save(file);
+        }
+        Command::Accept => match self.prompt.take() {
+          Some(Prompt::Overwrite(file)) => {
+            self.selected_file = Some(file);
+            self.confirm();
+          }
+          Some(Prompt::Rename(from, to)) => self.rename(&from, to),
+          Some(Prompt::Delete(paths)) => {
+            for path in &paths {
+              // Check again, as the folder may have changed since asking.
+              if !self.can_delete(&FileInfo::new(path.clone())) {
+                continue;
+              }
+
+              let result = match path.is_dir() {
+                true => fs::remove_dir_all(path),
+                false => fs::remove_file(path),
+              };
+
+              if let Err(err) = result {
+                println!("Error while deleting: {err}");
               }
-            })
-            .response
+            }
+            self.invalidate_cache();
+            self.refresh();
           }
-          Err(e) => ui.label(e.to_string()),
+          None => {}
         },
+        Command::Decline => self.prompt = None,
+        Command::Delete(paths) => self.prompt = Some(Prompt::Delete(paths)),
+        Command::StartRename(idx) => {
+          self.select_index(idx);
+          if let Some(info) = &self.selected_file {
+            self.filename_edit = get_file_name(info).to_string();
+            self.focus_filename = true;
+          }
+        }
+        Command::Sort(sort_by) => {
+          // Clicking the current column reverses the order.
+          self.sort_order = match (sort_by == self.sort_by, self.sort_order) {
+            (true, SortOrder::Ascending) => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+          };
+          self.sort_by = sort_by;
+          self.preserve_order = false;
+
+          let mut files = std::mem::replace(&mut self.files, Ok(Vec::new()));
+          if let Ok(files) = &mut files {
+            self.sort_files(files);
+          }
+          self.files = files;
+          self.range_start = None;
+          self.focused_index = None;
+        }
+        Command::Cancel => {
+          self.state = State::Cancelled;
+          self.close_reason = Some(CloseReason::CancelButton);
+        }
+        Command::CancelLoading => {
+          self.loading = None;
+          self.files = Err(io::ErrorKind::Interrupted.into());
+          self.error_path = Some(self.path.clone());
+        }
+        Command::Refresh => {
+          self.invalidate_cache();
+          self.refresh();
+        }
+        Command::UpDirectory => {
+          if let Some(parent) = self.path.parent() {
+            self.set_path(parent.to_path_buf());
+          }
+        }
+        Command::SetPath(path) => self.set_path(path),
+        Command::Back => {
+          self.history_back();
+        }
+        Command::Forward => {
+          self.history_forward();
+        }
+        Command::NewFolder => {
+          self.creating_folder = Some(self.new_folder_name_text.to_string());
+          self.focus_new_folder = true;
+        }
+        Command::CreateDirectory(name) => {
+          if let Err(err) = self.create_folder(&name) {
+            println!("Error while creating directory: {err}");
+          }
+        }
+        Command::CreateFile => {
+          let name = match self.filename_edit.is_empty() {
+            true => self.new_file_name_text.to_string(),
+            false => self.filename_edit.clone(),
+          };
+
+          if let Err(err) = self.create_file(&name) {
+            println!("Error while creating file: {err}");
+          }
+        }
+        Command::Rename(from, to) => {
+          let to = match &self.rename_hook {
+            Some(hook) => hook(&from, &to),
+            None => Ok(to),
+          };
+
+          match to {
+            // Renaming onto a different existing file replaces it, so ask first.
+            Ok(to) if self.confirm_overwrite && rename_collides(&from, &to) => {
+              self.prompt = Some(Prompt::Rename(from, to));
+            }
+            Ok(to) => self.rename(&from, to),
+            Err(msg) => self.rename_error = Some(msg),
+          }
+        }
+        Command::Copy(files) => self.copied_files = files,
+        Command::Paste => {
+          let mut pasted = None;
+          for from in &self.copied_files {
+            let Some(name) = from.file_name() else {
+              continue;
+            };
+
+            let to = get_copy_path(self.path.join(name));
+            match fs::copy(from, &to) {
+              Ok(_) => pasted = Some(to),
+              Err(err) => println!("Error while copying: {err}"),
+            }
+          }
+
+          if let Some(path) = pasted {
+            self.invalidate_cache();
+            self.refresh();
+            self.select(Some(FileInfo::new(path)));
+          }
+        }
+      };
+
+      let warn = self.warn_on_navigate && self.dialog_type == DialogType::SaveFile;
+      if warn && !self.filename_edit.is_empty() && self.path != prev_path {
+        // Show the hint for a few seconds.
+        self.location_changed_until = Some(ui.input(|state| state.time) + 3.0);
+      }
+    }
+  }
+
+  /// Icon shown before the name in the file list.
+  fn get_icon(&self, info: &FileInfo) -> Cow<'static, str> {
+    if info.broken_link {
+      return "⚠".into();
+    }
+
+    if let Some(icon) = self.icon.as_ref().and_then(|icon| icon(&info.path)) {
+      return icon.into();
+    }
+
+    if info.is_dir() {
+      return "🗀".into();
+    }
+
+    match self.file_icons {
+      true => get_file_icon(&info.path).into(),
+      false => "🗋".into(),
+    }
+  }
+
+  /// Name shown for a path outside of the file list, such as in breadcrumbs and prompts.
+  fn get_path_display_name<'a>(&self, path: &'a Path) -> Cow<'a, str> {
+    match (self.custom_display_name(path), path.file_name()) {
+      (Some(name), _) => name.into(),
+      (None, Some(name)) => name.to_string_lossy(),
+      (None, None) => path.to_string_lossy(),
+    }
+  }
+
+  /// Name from the `display_name_fn`, if it names the path.
+  fn custom_display_name(&self, path: &Path) -> Option<String> {
+    self
+      .display_name
+      .as_ref()
+      .and_then(|display_name| display_name(path))
+  }
+
+  /// Name of the only path, or the number of paths.
+  fn get_paths_label<'a>(&self, paths: &'a [PathBuf]) -> Cow<'a, str> {
+    match paths {
+      [path] => self.get_path_display_name(path),
+      _ => format!("{} {}", paths.len(), self.items_text).into(),
+    }
+  }
+
+  /// Draws a grid cell with the file's thumbnail, or its icon, over its name.
+  fn grid_cell(
+    &self,
+    ui: &mut Ui,
+    info: &FileInfo,
+    thumbnail: Option<&TextureHandle>,
+    selected: bool,
+    enabled: bool,
+  ) -> Response {
+    let sense = match enabled {
+      true => Sense::click(),
+      false => Sense::hover(),
+    };
+    let (rect, response) = ui.allocate_exact_size(GRID_CELL_SIZE, sense);
+    if !ui.is_rect_visible(rect) {
+      return response;
+    }
+
+    let visuals = ui.style().interact_selectable(&response, selected);
+    if selected || response.hovered() || response.highlighted() {
+      let painter = ui.painter();
+      painter.rect(
+        rect,
+        visuals.rounding,
+        visuals.weak_bg_fill,
+        visuals.bg_stroke,
       );
-    });
+    }
+
+    let color = match enabled {
+      true => visuals.text_color(),
+      false => ui.visuals().weak_text_color(),
+    };
+    let margin = ui.spacing().item_spacing.y;
+    let center = Pos2::new(
+      rect.center().x,
+      rect.top() + margin + GRID_THUMBNAIL_SIZE / 2.0,
+    );
+    match thumbnail {
+      Some(texture) => {
+        let size = texture.size_vec2();
+        let size = size * (GRID_THUMBNAIL_SIZE / size.max_elem());
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        let image_rect = Rect::from_center_size(center, size);
+        ui.painter()
+          .image(texture.id(), image_rect, uv, Color32::WHITE);
+      }
+      None => {
+        let font = FontId::proportional(GRID_THUMBNAIL_SIZE / 2.0);
+        let icon = self.get_icon(info);
+        ui.painter()
+          .text(center, Align2::CENTER_CENTER, icon, font, color);
+      }
+    }
+
+    // Long names are truncated, with the full name on hover.
+    let mut name = self.get_list_name(info).to_string();
+    if info.symlink && !info.broken_link {
+      name += " 🔗";
+    }
+    if info.writable == Some(false) {
+      name += " 🔒";
+    }
+
+    let font = egui::TextStyle::Body.resolve(ui.style());
+    let mut job = LayoutJob::simple_singleline(name.clone(), font, color);
+    job.wrap = TextWrapping {
+      max_rows: 1,
+      break_anywhere: true,
+      ..TextWrapping::wrap_at_width(rect.width() - margin * 2.0)
+    };
+    let galley = ui.fonts(|f| f.layout_job(job));
+    let size = galley.size();
+    let pos = Pos2::new(
+      rect.center().x - size.x / 2.0,
+      rect.bottom() - margin - size.y,
+    );
+    let elided = galley.elided;
+    ui.painter().galley(pos, galley, color);
+
+    match elided {
+      true => response.on_hover_text(name),
+      false => response,
+    }
+  }
+
+  /// Name shown in the file list, without the extension if `hide_extensions` is set.
+  fn get_list_name<'a>(&self, info: &'a FileInfo) -> Cow<'a, str> {
+    if !self.hide_extensions || info.is_dir() {
+      return self.get_display_name(info);
+    }
+
+    if let Some(name) = self.custom_display_name(&info.path) {
+      return name.into();
+    }
+
+    // Dotfiles such as ".bashrc" are all stem.
+    match info.path.file_stem().and_then(|stem| stem.to_str()) {
+      Some(stem) => stem.into(),
+      None => self.get_display_name(info),
+    }
+  }
+
+  /// Name shown in the file list and prompts.
+  fn get_display_name<'a>(&self, info: &'a FileInfo) -> Cow<'a, str> {
+    #[cfg(windows)]
+    if info.is_dir() && is_drive_root(&info.path) {
+      let name = self
+        .drive_display
+        .as_ref()
+        .and_then(|display| display(&info.path));
+      if let Some(name) = name {
+        return name.into();
+      }
+
+      if let Some(Some(label)) = self.drive_labels.get(&info.path) {
+        return label.clone().into();
+      }
+    }
 
-    if let Some(command) = command {
-      match command {
-        Command::Select(info) => self.select(Some(info)),
-        Command::MultiSelect(idx) => self.select_reset_multi(idx),
-        Command::MultiSelectRange(idx) => self.select_range(idx),
-        Command::MultiSelectSwitch(idx) => self.select_switch_multi(idx),
-        Command::Folder => {
-          let path = self.get_folder().to_owned();
-          self.selected_file = Some(FileInfo::new(path));
-          self.confirm();
-        }
-        Command::Open(path) => {
-          self.select(Some(path));
-          self.open_selected();
-        }
-        Command::OpenSelected => self.open_selected(),
-        Command::BrowseDirectory(dir) => {
-          self.selected_file = Some(dir);
-          self.open_selected();
-        }
-        Command::Save(file) => {
-          self.selected_file = Some(file);
-          self.confirm();
-        }
-        Command::Cancel => self.state = State::Cancelled,
-        Command::Refresh => self.refresh(),
-        Command::UpDirectory => {
-          if self.path.pop() {
-            self.refresh();
-          }
-        }
-        Command::CreateDirectory => {
-          let mut path = self.path.clone();
-          let name = match self.filename_edit.is_empty() {
-            true => self.new_folder_name_text.as_ref(),
-            false => self.filename_edit.as_ref(),
-          };
-          path.push(name);
-          match fs::create_dir(&path) {
-            Ok(_) => {
-              self.refresh();
-              self.select(Some(FileInfo::new(path)));
-              // TODO: scroll to selected?
-            }
-            Err(err) => println!("Error while creating directory: {err}"),
-          }
-        }
-        Command::Rename(from, to) => match fs::rename(from, &to) {
-          Ok(_) => {
-            self.refresh();
-            self.select(Some(FileInfo::new(to)));
-          }
-          Err(err) => println!("Error while renaming: {err}"),
-        },
-      };
+    match self.custom_display_name(&info.path) {
+      Some(name) => name.into(),
+      None => get_file_name(info).into(),
+    }
+  }
+
+  /// Reads the label of one listed drive that hasn't been labeled yet. Only one per frame since
+  /// a drive can be slow to answer, such as a network drive.
+  #[cfg(windows)]
+  fn update_drive_labels(&mut self, ctx: &Context) {
+    let Ok(files) = &self.files else {
+      return;
+    };
+
+    let mut unlabeled = files
+      .iter()
+      .filter(|info| info.is_dir() && is_drive_root(&info.path))
+      .filter(|info| !self.drive_labels.contains_key(&info.path))
+      .map(|info| info.path.clone());
+    let Some(path) = unlabeled.next() else {
+      return;
+    };
+
+    if unlabeled.next().is_some() {
+      ctx.request_repaint();
     }
+
+    let label = get_drive_label(&path);
+    self.drive_labels.insert(path, label);
   }
 
   fn get_folder(&self) -> &Path {
@@ -922,83 +5024,687 @@ impl FileDialog {
     &self.path
   }
 
-  fn read_folder(&self) -> Result<Vec<FileInfo>, Error> {
-    fs::read_dir(&self.path).map(|entries| {
-      let mut file_infos: Vec<FileInfo> = entries
-        .filter_map(|result| result.ok())
-        .filter_map(|entry| {
-          let info = FileInfo::new(entry.path());
-          if !info.is_dir() {
-            if !self.show_system_files && !info.path.is_file() {
-              // Do not show system files.
-              return None;
-            }
+  fn sort_files(&self, files: &mut [FileInfo]) {
+    let sort_by = self.sort_by;
+    let sort_order = self.sort_order;
+    if let Some(sort) = &self.sort {
+      files.sort_by(|a, b| match sort_order {
+        SortOrder::Ascending => sort(&a.path, &b.path),
+        SortOrder::Descending => sort(&b.path, &a.path),
+      });
+      return;
+    }
 
-            // Filter.
-            if !(self.show_files_filter)(&info.path) {
-              return None;
-            }
-          }
+    let compare_names = |a: &FileInfo, b: &FileInfo| {
+      let (a, b) = (get_file_name(a), get_file_name(b));
+      let ordering = match (self.natural_sort, self.case_insensitive_sort) {
+        (true, ignore_case) => natural_cmp(a, b, ignore_case),
+        (false, true) => a
+          .chars()
+          .flat_map(char::to_lowercase)
+          .cmp(b.chars().flat_map(char::to_lowercase)),
+        (false, false) => Ordering::Equal,
+      };
+
+      // Fall back to byte order so that the order is stable.
+      ordering.then_with(|| a.cmp(b))
+    };
+
+    // Sort with folders before files.
+    files.sort_by(|a, b| match b.is_dir().cmp(&a.is_dir()) {
+      Ordering::Less => Ordering::Less,
+      Ordering::Equal => {
+        let ordering = match sort_by {
+          SortBy::Name => Ordering::Equal,
+          SortBy::Size => a.size.cmp(&b.size),
+          SortBy::Modified => a.modified.cmp(&b.modified),
+          SortBy::Type => a.path.extension().cmp(&b.path.extension()),
+        }
+        .then_with(|| compare_names(a, b));
+
+        match sort_order {
+          SortOrder::Ascending => ordering,
+          SortOrder::Descending => ordering.reverse(),
+        }
+      }
+      Ordering::Greater => Ordering::Greater,
+    });
+  }
+
+  fn read_folder(&mut self) -> Result<Vec<FileInfo>, Error> {
+    let entries = read_entries(&self.path)?;
+    self.cached_entries = Some((self.path.clone(), entries.clone()));
+    Ok(self.filter_entries(entries))
+  }
 
-          #[cfg(unix)]
-          if !self.show_hidden && get_file_name(&info).starts_with('.') {
+  /// Entries read for the current folder earlier, if they're still cached.
+  fn cached_entries(&self) -> Option<Vec<FileInfo>> {
+    match &self.cached_entries {
+      Some((path, entries)) if *path == self.path => Some(entries.clone()),
+      _ => None,
+    }
+  }
+
+  /// Filters and sorts the entries of the current folder.
+  fn filter_entries(&self, entries: Vec<FileInfo>) -> Vec<FileInfo> {
+    let mut file_infos: Vec<FileInfo> = entries
+      .into_iter()
+      .filter_map(|info| {
+        // Broken links were listed with the system files before they had their own option.
+        if info.broken_link {
+          if !self.show_broken_symlinks && !self.show_system_files {
             return None;
           }
+        } else if !info.is_dir() && !self.show_system_files && !info.path.is_file() {
+          // Do not show system files.
+          return None;
+        }
 
-          Some(info)
-        })
-        .collect();
+        // Filter.
+        let filtered = (self.show_files_filter)(&info.path) && self.passes_named_filter(&info.path);
+        if !info.is_dir() && !filtered {
+          return None;
+        }
 
-      // Sort with folders before files.
-      file_infos.sort_by(|a, b| match b.is_dir().cmp(&a.is_dir()) {
-        Ordering::Less => Ordering::Less,
-        Ordering::Equal => a.path.file_name().cmp(&b.path.file_name()),
-        Ordering::Greater => Ordering::Greater,
-      });
+        if !self.show_hidden && info.hidden {
+          return None;
+        }
 
-      #[cfg(windows)]
-      let file_infos = match self.show_drives {
-        true => {
-          let drives = get_drives();
-          let mut infos = Vec::with_capacity(drives.len() + file_infos.len());
-          for drive in drives {
+        Some(info)
+      })
+      .collect();
+
+    if !self.preserve_order {
+      self.sort_files(&mut file_infos);
+    }
+
+    #[cfg(windows)]
+    let file_infos = match self.show_drives {
+      true => {
+        let drives = get_drives();
+        let mut infos = Vec::with_capacity(drives.len() + file_infos.len());
+        for drive in drives {
+          if (self.drive_filter)(&drive) {
             infos.push(FileInfo::new(drive));
           }
-          infos.append(&mut file_infos);
-          infos
         }
-        false => file_infos,
-      };
+        infos.append(&mut file_infos);
+        infos
+      }
+      false => file_infos,
+    };
 
-      file_infos
-    })
+    file_infos
+  }
+
+  /// Takes the entries read in the background once they're ready.
+  fn poll_loading(&mut self, ctx: &Context) {
+    let Some(loading) = &self.loading else {
+      return;
+    };
+
+    if !loading.is_finished() {
+      ctx.request_repaint_after_secs(0.05);
+      return;
+    }
+
+    if let Some(loading) = self.loading.take() {
+      let result = loading
+        .join()
+        .unwrap_or_else(|_| Err(Error::other("Folder read failed")));
+      if let Ok(entries) = &result {
+        self.cached_entries = Some((self.path.clone(), entries.clone()));
+      }
+
+      self.files = result.map(|entries| self.filter_entries(entries));
+      self.error_path = self.files.is_err().then(|| self.path.clone());
+      if self.auto_select_single {
+        self.select_single_file();
+      }
+      self.select_pending();
+      self.reveal_pending();
+    }
+  }
+
+  /// Names of the folders that complete the last segment of the path field.
+  fn read_path_completions(&self) -> Vec<String> {
+    let Some((dir, partial)) = split_path_edit(&self.path_edit) else {
+      return Vec::new();
+    };
+
+    let Ok(entries) = read_entries(dir) else {
+      return Vec::new();
+    };
+
+    let partial = partial.to_lowercase();
+    let mut names: Vec<String> = entries
+      .into_iter()
+      .filter(|info| info.is_dir() && (self.show_hidden || !info.hidden))
+      .filter(|info| self.within_root(&info.path))
+      .map(|info| get_file_name(&info).to_string())
+      .filter(|name| name.to_lowercase().starts_with(&partial))
+      .collect();
+    names.sort_by(|a, b| natural_cmp(a, b, true));
+    names
+  }
+
+  /// Re-reads the folder when the window regains focus or the refresh interval has passed.
+  fn poll_refresh(&mut self, ctx: &Context) {
+    if let Some(reloading) = &self.reloading {
+      if !reloading.is_finished() {
+        ctx.request_repaint_after_secs(0.05);
+        return;
+      }
+
+      if let Some(reloading) = self.reloading.take() {
+        let result = reloading
+          .join()
+          .unwrap_or_else(|_| Err(Error::other("Folder read failed")));
+        if let Ok(entries) = &result {
+          self.cached_entries = Some((self.path.clone(), entries.clone()));
+        }
+
+        let result = result.map(|entries| self.filter_entries(entries));
+        self.apply_reload(result);
+      }
+    }
+
+    let focused = ctx.input(|state| state.viewport().focused.unwrap_or(true));
+    let regained = focused && !self.window_focused;
+    self.window_focused = focused;
+
+    let due = match self.refresh_interval {
+      Some(interval) => {
+        let elapsed = self.refreshed_at.elapsed();
+        ctx.request_repaint_after(interval.saturating_sub(elapsed));
+        elapsed >= interval
+      }
+      None => false,
+    };
+
+    if self.loading.is_none() && ((self.refresh_on_focus && regained) || due) {
+      self.reload();
+    }
+  }
+
+  /// Re-reads the folder, keeping the selection and keyboard focus on the same paths. Unlike
+  /// `refresh`, the search and filename fields are left alone.
+  fn reload(&mut self) {
+    self.invalidate_cache();
+    if self.files.is_err() {
+      self.refresh();
+      return;
+    }
+
+    self.refreshed_at = Instant::now();
+    if self.background_loading {
+      let path = self.path.clone();
+      self.reloading = Some(thread::spawn(move || read_entries(&path)));
+    } else {
+      let result = self.read_folder();
+      self.apply_reload(result);
+    }
+  }
+
+  /// Lists the re-read entries of the folder, see `reload`.
+  fn apply_reload(&mut self, result: Result<Vec<FileInfo>, Error>) {
+    let Ok(files) = &self.files else {
+      return;
+    };
+
+    let path_at = |idx: Option<usize>| Some(files.get(idx?)?.path.clone());
+    let focused = path_at(self.focused_index);
+    let range_start = path_at(self.range_start);
+    let selected: HashSet<PathBuf> = files
+      .iter()
+      .filter(|info| info.selected)
+      .map(|info| info.path.clone())
+      .collect();
+
+    match result {
+      Ok(mut files) => {
+        for info in &mut files {
+          info.selected = selected.contains(&info.path);
+        }
+
+        let index_of = |path: Option<PathBuf>| {
+          let path = path?;
+          files.iter().position(|info| info.path == path)
+        };
+        self.focused_index = index_of(focused);
+        self.range_start = index_of(range_start);
+        self.files = Ok(files);
+      }
+      Err(err) => {
+        self.files = Err(err);
+        self.error_path = Some(self.path.clone());
+      }
+    }
+
+    // A file deleted by another program is no longer selected.
+    if let Some(info) = self.selected_file.take() {
+      self.selected_file = Some(FileInfo::new(info.path)).filter(|info| info.path.exists());
+    }
+
+    self.folder_counts.clear();
+    self.drag = None;
   }
 }
 
+/// Rows being selected by dragging across the list.
+#[derive(Debug)]
+struct DragSelect {
+  /// Row where the drag started.
+  start: usize,
+  /// Row under the pointer.
+  current: usize,
+  /// Selection before the drag started.
+  snapshot: Vec<bool>,
+  /// Add to the selection instead of replacing it.
+  additive: bool,
+}
+
+/// Action waiting for the user to confirm it.
+#[derive(Debug)]
+enum Prompt {
+  /// Save over an existing file.
+  Overwrite(FileInfo),
+  /// Rename a file over an existing one.
+  Rename(PathBuf, PathBuf),
+  /// Delete files and folders.
+  Delete(Vec<PathBuf>),
+}
+
 #[derive(Clone, Debug, Default)]
-struct FileInfo {
+/// File or folder listed by the dialog.
+pub struct FileInfo {
   path: PathBuf,
   file_type: Option<FileType>,
+  size: Option<u64>,
+  modified: Option<SystemTime>,
+  broken_link: bool,
+  symlink: bool,
+  hidden: bool,
+  writable: Option<bool>,
+  kind: FileKind,
   selected: bool,
 }
 
 impl FileInfo {
   fn new(path: PathBuf) -> Self {
-    let file_type = fs::metadata(&path).ok().map(|meta| meta.file_type());
+    let meta = fs::metadata(&path).ok();
+    let file_type = meta.as_ref().map(|meta| meta.file_type());
+    let size = meta
+      .as_ref()
+      .filter(|meta| meta.is_file())
+      .map(|meta| meta.len());
+    let modified = meta.as_ref().and_then(|meta| meta.modified().ok());
+    let writable = meta.as_ref().and_then(get_writable);
+
+    // A symbolic link whose target can't be read is broken.
+    let link_type = fs::symlink_metadata(&path)
+      .ok()
+      .map(|meta| meta.file_type());
+    let symlink = link_type.is_some_and(|link_type| link_type.is_symlink());
+    let broken_link = symlink && file_type.is_none();
+    let kind = link_type.map_or(FileKind::Unknown, get_file_kind);
+
+    // Hidden by an attribute on Windows and by a leading dot elsewhere.
+    #[cfg(windows)]
+    let hidden = {
+      use std::os::windows::fs::MetadataExt;
+      const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+      let attributes = meta.as_ref().map(|meta| meta.file_attributes());
+      attributes.is_some_and(|attributes| attributes & FILE_ATTRIBUTE_HIDDEN != 0)
+    };
+    #[cfg(not(windows))]
+    let hidden = path
+      .file_name()
+      .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
     Self {
       path,
       file_type,
+      size,
+      modified,
+      broken_link,
+      symlink,
+      hidden,
+      writable,
+      kind,
       selected: false,
     }
   }
 
-  fn is_file(&self) -> bool {
+  /// Returns `true` if it's a file, following symbolic links.
+  pub fn is_file(&self) -> bool {
     self.file_type.is_some_and(|file_type| file_type.is_file())
   }
 
-  fn is_dir(&self) -> bool {
+  /// Returns `true` if it's a folder, following symbolic links.
+  pub fn is_dir(&self) -> bool {
     self.file_type.is_some_and(|file_type| file_type.is_dir())
   }
+
+  /// Full path.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Size in bytes of a file, or `None` for folders.
+  pub fn size(&self) -> Option<u64> {
+    self.size
+  }
+
+  /// Last modified time, if it could be read.
+  pub fn modified(&self) -> Option<SystemTime> {
+    self.modified
+  }
+
+  /// Returns `true` if it's a symbolic link.
+  pub fn is_symlink(&self) -> bool {
+    self.symlink
+  }
+
+  /// Kind of the entry itself, such as a symbolic link or, on Unix, a socket or device.
+  pub fn file_kind(&self) -> FileKind {
+    self.kind
+  }
+
+  /// Returns `Some(false)` if it's read-only, or `None` if that isn't known.
+  pub fn writable(&self) -> Option<bool> {
+    self.writable
+  }
+
+  /// Returns `true` if it's hidden.
+  pub fn is_hidden(&self) -> bool {
+    self.hidden
+  }
+
+  /// Returns `true` if it's selected in multi-select mode.
+  pub fn is_selected(&self) -> bool {
+    self.selected
+  }
+}
+
+/// Compares names, treating runs of digits as numbers.
+fn natural_cmp(a: &str, b: &str, ignore_case: bool) -> Ordering {
+  let mut a = a.chars().peekable();
+  let mut b = b.chars().peekable();
+  loop {
+    match (a.peek().copied(), b.peek().copied()) {
+      (None, None) => return Ordering::Equal,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+        let take_number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+          let mut digits = String::new();
+          while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+          }
+          digits.trim_start_matches('0').to_string()
+        };
+
+        // Without leading zeros, a longer number is larger.
+        let (x, y) = (take_number(&mut a), take_number(&mut b));
+        let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+        if ordering != Ordering::Equal {
+          return ordering;
+        }
+      }
+      (Some(x), Some(y)) => {
+        let ordering = match ignore_case {
+          true => x.to_lowercase().cmp(y.to_lowercase()),
+          false => x.cmp(&y),
+        };
+        if ordering != Ordering::Equal {
+          return ordering;
+        }
+        a.next();
+        b.next();
+      }
+    }
+  }
+}
+
+/// Icon for common file types, based on the extension.
+fn get_file_icon(path: &Path) -> &'static str {
+  let ext = path
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+
+  match ext.as_deref() {
+    Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp" | "ico" | "tif" | "tiff") => "🖼",
+    Some("zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar") => "📦",
+    Some("mp3" | "wav" | "flac" | "ogg" | "opus" | "m4a") => "🎵",
+    Some("mp4" | "mkv" | "avi" | "mov" | "webm") => "🎞",
+    Some("txt" | "md" | "pdf" | "doc" | "docx" | "odt" | "rtf") => "🗐",
+    Some(
+      "rs" | "c" | "h" | "cpp" | "hpp" | "py" | "js" | "ts" | "go" | "java" | "sh" | "toml"
+      | "json" | "yaml" | "yml" | "xml" | "html" | "css",
+    ) => "📝",
+    _ => "🗋",
+  }
+}
+
+/// The user's home folder, if it's known.
+fn get_home_dir() -> Option<PathBuf> {
+  #[cfg(windows)]
+  let home = env::var_os("USERPROFILE");
+  #[cfg(not(windows))]
+  let home = env::var_os("HOME");
+
+  home.filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+/// Splits the path field into the folder to complete in and the partial name after it.
+fn split_path_edit(text: &str) -> Option<(&Path, &str)> {
+  let index = text.rfind(is_separator)?;
+  Some((Path::new(&text[..=index]), &text[index + 1..]))
+}
+
+/// Size of a text field that fills the rest of the row, or has the width if it fits.
+fn field_size(ui: &Ui, width: Option<f32>) -> Vec2 {
+  let available = ui.available_size();
+  match width {
+    Some(width) => Vec2::new(width.min(available.x), available.y),
+    None => available,
+  }
+}
+
+/// Selects the characters from `start` to `end` in a text field.
+fn select_text(ctx: &Context, id: Id, start: usize, end: usize) {
+  let mut state = TextEditState::load(ctx, id).unwrap_or_default();
+  let range = CCursorRange::two(CCursor::new(start), CCursor::new(end));
+  state.cursor.set_char_range(Some(range));
+  state.store(ctx, id);
+}
+
+/// Icon put before the window title.
+fn title_icon(dialog_type: DialogType) -> &'static str {
+  match dialog_type {
+    DialogType::SelectFolder => "📁  ",
+    DialogType::OpenFile => "📂  ",
+    DialogType::SaveFile => "💾  ",
+  }
+}
+
+/// Window title text used when none is set.
+fn default_title(dialog_type: DialogType) -> &'static str {
+  match dialog_type {
+    DialogType::SelectFolder => "Select Folder",
+    DialogType::OpenFile => "Open File",
+    DialogType::SaveFile => "Save File",
+  }
+}
+
+/// Reads the entries of a folder, without filtering them.
+fn read_entries(path: &Path) -> Result<Vec<FileInfo>, Error> {
+  fs::read_dir(path).map(|entries| {
+    entries
+      .filter_map(|result| result.ok())
+      .map(|entry| FileInfo::new(entry.path()))
+      .collect()
+  })
+}
+
+/// Human readable file size, e.g. "1.5 MiB".
+fn format_size(size: u64) -> String {
+  const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+  if size < 1024 {
+    return format!("{size} B");
+  }
+
+  let mut size = size as f64 / 1024.0;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Returns `Some(false)` if the metadata is read-only. Windows ignores the read-only attribute
+/// on folders, so it's unknown for them there.
+fn get_writable(meta: &Metadata) -> Option<bool> {
+  if cfg!(windows) && meta.is_dir() {
+    return None;
+  }
+
+  Some(!meta.permissions().readonly())
+}
+
+/// Kind of the entry a file type describes.
+fn get_file_kind(file_type: FileType) -> FileKind {
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::FileTypeExt;
+    if file_type.is_socket() {
+      return FileKind::Socket;
+    }
+    if file_type.is_fifo() {
+      return FileKind::Fifo;
+    }
+    if file_type.is_block_device() {
+      return FileKind::BlockDevice;
+    }
+    if file_type.is_char_device() {
+      return FileKind::CharDevice;
+    }
+  }
+
+  if file_type.is_symlink() {
+    FileKind::Symlink
+  } else if file_type.is_dir() {
+    FileKind::Dir
+  } else if file_type.is_file() {
+    FileKind::File
+  } else {
+    FileKind::Unknown
+  }
+}
+
+/// Width of the text in the button font.
+fn text_width(ui: &Ui, text: &str) -> f32 {
+  let font = egui::TextStyle::Button.resolve(ui.style());
+  let color = ui.visuals().text_color();
+  ui.fonts(|f| f.layout_no_wrap(text.to_string(), font, color))
+    .size()
+    .x
+}
+
+/// Text shortened with an ellipsis to fit in the width, see `TruncateStyle`.
+fn truncate_text<'a>(ui: &Ui, text: &'a str, width: f32, style: TruncateStyle) -> Cow<'a, str> {
+  if style == TruncateStyle::None || text_width(ui, text) <= width {
+    return Cow::Borrowed(text);
+  }
+
+  let chars: Vec<char> = text.chars().collect();
+  let shorten = |keep: usize| -> String {
+    let start = |count: usize| chars[..count].iter().collect::<String>();
+    let end = |count: usize| chars[chars.len() - count..].iter().collect::<String>();
+    match style {
+      TruncateStyle::Start => format!("…{}", end(keep)),
+      TruncateStyle::Middle => format!("{}…{}", start(keep - keep / 2), end(keep / 2)),
+      _ => format!("{}…", start(keep)),
+    }
+  };
+
+  // Keep as many characters as fit.
+  let (mut low, mut high) = (0, chars.len().saturating_sub(1));
+  while low < high {
+    let mid = (low + high).div_ceil(2);
+    match text_width(ui, &shorten(mid)) <= width {
+      true => low = mid,
+      false => high = mid - 1,
+    }
+  }
+  Cow::Owned(shorten(low))
+}
+
+/// Modified time as "YYYY-MM-DD HH:MM" in UTC.
+fn format_modified(time: SystemTime) -> String {
+  let Ok(duration) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+    return String::new();
+  };
+
+  let secs = duration.as_secs();
+  let (hour, minute) = (secs / 3600 % 24, secs / 60 % 60);
+
+  // Convert days since the epoch to a civil date (Howard Hinnant's algorithm).
+  let days = (secs / 86400) as i64 + 719_468;
+  let era = days.div_euclid(146_097);
+  let doe = days.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}")
+}
+
+/// Returns `true` if renaming would replace a different existing file. Changing only the case of a
+/// name on a case-insensitive file system isn't a collision.
+fn rename_collides(from: &Path, to: &Path) -> bool {
+  to.exists() && fs::canonicalize(from).ok() != fs::canonicalize(to).ok()
+}
+
+/// Returns `true` if the name contains the lowercase filter text, ignoring case.
+fn matches_filter(filter: &str, name: &str) -> bool {
+  filter.is_empty() || name.to_lowercase().contains(filter)
+}
+
+/// Get a path that doesn't collide with an existing file, i.e. "name (copy).ext".
+fn get_copy_path(path: PathBuf) -> PathBuf {
+  if !path.exists() {
+    return path;
+  }
+
+  let stem = path
+    .file_stem()
+    .and_then(|stem| stem.to_str())
+    .unwrap_or_default();
+  let ext = path.extension().and_then(|ext| ext.to_str());
+  let mut count = 1;
+  loop {
+    let name = match count {
+      1 => format!("{stem} (copy)"),
+      _ => format!("{stem} (copy {count})"),
+    };
+
+    let name = match ext {
+      Some(ext) => format!("{name}.{ext}"),
+      None => name,
+    };
+
+    let copy = path.with_file_name(name);
+    if !copy.exists() {
+      return copy;
+    }
+    count += 1;
+  }
 }
 
 #[cfg(windows)]
@@ -1016,6 +5722,49 @@ fn get_drives() -> Vec<PathBuf> {
   drive_names
 }
 
+/// Drive root named like "Local Disk (C:) — 42.0 GiB free", or `None` if the drive can't be
+/// queried, such as a removable drive with no media.
+#[cfg(windows)]
+fn get_drive_label(path: &Path) -> Option<String> {
+  use std::{os::windows::ffi::OsStrExt, ptr};
+
+  let root: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+  let mut name = [0u16; 261];
+  let ok = unsafe {
+    GetVolumeInformationW(
+      root.as_ptr(),
+      name.as_mut_ptr(),
+      name.len() as u32,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      ptr::null_mut(),
+      ptr::null_mut(),
+      0,
+    )
+  };
+  if ok == 0 {
+    return None;
+  }
+
+  let mut free = 0u64;
+  let ok =
+    unsafe { GetDiskFreeSpaceExW(root.as_ptr(), &mut free, ptr::null_mut(), ptr::null_mut()) };
+  if ok == 0 {
+    return None;
+  }
+
+  let len = name.iter().position(|&ch| ch == 0).unwrap_or(name.len());
+  let name = String::from_utf16_lossy(&name[..len]);
+  let name = match name.is_empty() {
+    true => "Local Disk",
+    false => name.as_str(),
+  };
+
+  let letter = path.to_string_lossy();
+  let letter = letter.trim_end_matches('\\');
+  Some(format!("{name} ({letter}) — {} free", format_size(free)))
+}
+
 #[cfg(windows)]
 fn is_drive_root(path: &Path) -> bool {
   path
@@ -1038,6 +5787,143 @@ fn get_file_name(info: &FileInfo) -> &str {
 }
 
 #[cfg(windows)]
-extern "C" {
-  pub fn GetLogicalDrives() -> u32;
+extern "system" {
+  fn GetLogicalDrives() -> u32;
+  fn GetVolumeInformationW(
+    root_path_name: *const u16,
+    volume_name_buffer: *mut u16,
+    volume_name_size: u32,
+    volume_serial_number: *mut u32,
+    maximum_component_length: *mut u32,
+    file_system_flags: *mut u32,
+    file_system_name_buffer: *mut u16,
+    file_system_name_size: u32,
+  ) -> i32;
+  fn GetDiskFreeSpaceExW(
+    directory_name: *const u16,
+    free_bytes_available_to_caller: *mut u64,
+    total_number_of_bytes: *mut u64,
+    total_number_of_free_bytes: *mut u64,
+  ) -> i32;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Runs a frame of the dialog with Enter pressed.
+  fn press_enter(ctx: &Context, dialog: &mut FileDialog) {
+    let event = Event::Key {
+      key: Key::Enter,
+      physical_key: None,
+      pressed: true,
+      repeat: false,
+      modifiers: Modifiers::NONE,
+    };
+    let input = egui::RawInput {
+      events: vec![event],
+      ..Default::default()
+    };
+    let _ = ctx.run(input, |ctx| {
+      dialog.show(ctx);
+    });
+  }
+
+  /// Empty folder in the temporary folder, unique to the test.
+  fn temp_folder(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("egui_file_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+  }
+
+  #[test]
+  fn enter_browses_into_selected_folder() {
+    let root = temp_folder("enter_browse");
+    let folder = root.join("folder");
+    fs::create_dir(&folder).unwrap();
+
+    let ctx = Context::default();
+    let mut dialog = FileDialog::open_file(Some(root.clone()));
+    dialog.open();
+    dialog.select(Some(FileInfo::new(folder.clone())));
+    press_enter(&ctx, &mut dialog);
+
+    assert_eq!(dialog.directory(), folder);
+    assert_eq!(dialog.state(), State::Open);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn editing_filename_drops_overwrite_prompt() {
+    let root = temp_folder("overwrite_prompt");
+    let file = root.join("a.txt");
+    fs::write(&file, "").unwrap();
+
+    let ctx = Context::default();
+    let mut dialog = FileDialog::save_file(Some(root.clone())).confirm_overwrite(true);
+    dialog.open();
+    dialog.filename_edit = "a.txt".into();
+    dialog.save(FileInfo::new(file));
+    assert!(matches!(dialog.prompt, Some(Prompt::Overwrite(_))));
+
+    dialog.filename_edit = "b.txt".into();
+    let _ = ctx.run(Default::default(), |ctx| {
+      dialog.show(ctx);
+    });
+
+    assert!(dialog.prompt.is_none());
+    assert_eq!(dialog.state(), State::Open);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn enter_ignores_empty_multi_selection() {
+    let root = temp_folder("enter_empty");
+    fs::write(root.join("file.txt"), "").unwrap();
+
+    let ctx = Context::default();
+    let mut dialog = FileDialog::open_file(Some(root.clone())).multi_select(true);
+    dialog.open();
+    press_enter(&ctx, &mut dialog);
+
+    assert_eq!(dialog.state(), State::Open);
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn enter_opens_typed_file_hidden_by_filter() {
+    let root = temp_folder("typed_hidden");
+    let file = root.join("hidden.txt");
+    fs::write(&file, "").unwrap();
+
+    let ctx = Context::default();
+    let mut dialog =
+      FileDialog::open_file(Some(root.clone())).show_files_filter(Box::new(|_| false));
+    dialog.open();
+    dialog.filename_edit = "hidden.txt".into();
+    press_enter(&ctx, &mut dialog);
+
+    assert_eq!(dialog.state(), State::Selected);
+    assert_eq!(dialog.path_absolute(), Some(file.as_path()));
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn enter_ignores_typed_file_rejected_by_filename_filter() {
+    let root = temp_folder("typed_rejected");
+    fs::write(root.join("rejected.txt"), "").unwrap();
+
+    let ctx = Context::default();
+    let mut dialog = FileDialog::open_file(Some(root.clone()))
+      .filename_filter(Box::new(|name| !name.starts_with("rejected")));
+    dialog.open();
+    dialog.filename_edit = "rejected.txt".into();
+    press_enter(&ctx, &mut dialog);
+    assert!(!dialog.confirm_selection());
+
+    assert_eq!(dialog.state(), State::Open);
+    assert_eq!(dialog.path_absolute(), None);
+    fs::remove_dir_all(&root).unwrap();
+  }
 }